@@ -17,8 +17,9 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use rand::SeedableRng;
 use unit_root::prelude::distrib::Regression;
+use unit_root::prelude::nalgebra::DVector;
 use unit_root::prelude::tools::adf_test;
-use unit_root::utils::gen_ar_1;
+use unit_root::utils::{gen_ar_1, seasonal_diff};
 
 fn adf_benchmark_f32_2(c: &mut Criterion) {
     for size in [100, 200, 500, 1000, 5000].iter() {
@@ -84,11 +85,28 @@ fn adf_benchmark_f64_10(c: &mut Criterion) {
     }
 }
 
+fn diff_benchmark_f64(c: &mut Criterion) {
+    let size = 5000;
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+    let y: DVector<f64> = gen_ar_1(&mut rng, size, 0., 0.5, 1.0);
+
+    // the subtraction of two row views, as prepare used to difference y
+    c.bench_with_input(BenchmarkId::new("diff_f64_views", size), &y, |b, y| {
+        b.iter(|| y.rows(1, y.len() - 1) - y.rows(0, y.len() - 1))
+    });
+
+    // the subtraction over contiguous slices that prepare uses now
+    c.bench_with_input(BenchmarkId::new("diff_f64_slices", size), &y, |b, y| {
+        b.iter(|| seasonal_diff(y, 1).unwrap())
+    });
+}
+
 criterion_group!(
     benches,
     adf_benchmark_f32_2,
     adf_benchmark_f64_2,
     adf_benchmark_f32_10,
-    adf_benchmark_f64_10
+    adf_benchmark_f64_10,
+    diff_benchmark_f64
 );
 criterion_main!(benches);
@@ -15,14 +15,14 @@
 //! OLS benchmark
 #![allow(missing_docs)]
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
-use unit_root::regression::ols;
+use unit_root::regression::{ols, ols_cholesky};
 use unit_root::utils::gen_affine_data;
 
 fn ols_benchmark_f32(c: &mut Criterion) {
     for size in [100, 200, 500, 1000, 5000].iter() {
         let mu: f32 = 0.;
         let beta = 0.5;
-        let (x, y) = gen_affine_data(*size, mu, beta);
+        let (x, y) = gen_affine_data(*size, mu, beta).unwrap();
 
         c.bench_with_input(BenchmarkId::new("ols_f32", size), &(y, x), |b, yx| {
             let (y, x) = yx;
@@ -36,7 +36,7 @@ fn ols_benchmark_f64(c: &mut Criterion) {
     for size in [100, 200, 500, 1000, 5000].iter() {
         let mu: f64 = 0.;
         let beta = 0.5;
-        let (x, y) = gen_affine_data(*size, mu, beta);
+        let (x, y) = gen_affine_data(*size, mu, beta).unwrap();
 
         c.bench_with_input(BenchmarkId::new("ols_f64", size), &(y, x), |b, yx| {
             let (y, x) = yx;
@@ -46,5 +46,47 @@ fn ols_benchmark_f64(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, ols_benchmark_f32, ols_benchmark_f64);
+fn ols_cholesky_benchmark_f32(c: &mut Criterion) {
+    for size in [100, 200, 500, 1000, 5000].iter() {
+        let mu: f32 = 0.;
+        let beta = 0.5;
+        let (x, y) = gen_affine_data(*size, mu, beta).unwrap();
+
+        c.bench_with_input(
+            BenchmarkId::new("ols_cholesky_f32", size),
+            &(y, x),
+            |b, yx| {
+                let (y, x) = yx;
+
+                b.iter(|| ols_cholesky(y, x))
+            },
+        );
+    }
+}
+
+fn ols_cholesky_benchmark_f64(c: &mut Criterion) {
+    for size in [100, 200, 500, 1000, 5000].iter() {
+        let mu: f64 = 0.;
+        let beta = 0.5;
+        let (x, y) = gen_affine_data(*size, mu, beta).unwrap();
+
+        c.bench_with_input(
+            BenchmarkId::new("ols_cholesky_f64", size),
+            &(y, x),
+            |b, yx| {
+                let (y, x) = yx;
+
+                b.iter(|| ols_cholesky(y, x))
+            },
+        );
+    }
+}
+
+criterion_group!(
+    benches,
+    ols_benchmark_f32,
+    ols_benchmark_f64,
+    ols_cholesky_benchmark_f32,
+    ols_cholesky_benchmark_f64
+);
 criterion_main!(benches);
@@ -0,0 +1,169 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chi-square distribution
+use num_traits::Float;
+
+use super::ln_gamma;
+
+/// Returns the cumulative distribution function of the chi-square distribution with df
+/// degrees of freedom at x: P(X <= x), e.g. for likelihood-ratio and portmanteau
+/// statistics.
+///
+/// Evaluated through the regularized lower incomplete gamma function P(df / 2, x / 2).
+/// Returns 0 for x <= 0 and NaN if df is 0.
+///
+/// # Examples:
+/// ```rust
+/// use unit_root::prelude::distrib::chi2::chi2_cdf;
+///
+/// // the 95% quantile with 1 degree of freedom
+/// assert!((chi2_cdf(3.841458820694f64, 1) - 0.95).abs() < 1e-9);
+/// ```
+pub fn chi2_cdf<F: Float>(x: F, df: usize) -> F {
+    if df == 0 {
+        return F::nan();
+    }
+
+    let (lower, _upper) = incomplete_gamma(df as f64 / 2.0, x.to_f64().unwrap_or(f64::NAN) / 2.0);
+    F::from(lower).unwrap_or_else(F::nan)
+}
+
+/// Survival function of the chi-square distribution with df degrees of freedom:
+/// P(X > x), without the cancellation of 1 - [`chi2_cdf`] in the upper tail.
+pub(crate) fn chi_square_survival(x: f64, df: f64) -> f64 {
+    incomplete_gamma(df / 2.0, x / 2.0).1
+}
+
+/// Returns the regularized lower and upper incomplete gamma functions (P(a, x), Q(a, x)),
+/// for a > 0 and x >= 0, with P + Q = 1.
+///
+/// Uses the series expansion of P(a, x) for x < a + 1 and the continued fraction of
+/// Q(a, x) with the modified Lentz method otherwise, the other one being the complement.
+fn incomplete_gamma(a: f64, x: f64) -> (f64, f64) {
+    const MAX_ITERATIONS: usize = 300;
+    const EPSILON: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+
+    if x.is_nan() {
+        return (f64::NAN, f64::NAN);
+    }
+    if x <= 0.0 {
+        return (0.0, 1.0);
+    }
+
+    let ln_front = a * x.ln() - x - ln_gamma(a);
+
+    if x < a + 1.0 {
+        let mut ap = a;
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for _ in 0..MAX_ITERATIONS {
+            ap += 1.0;
+            term *= x / ap;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+
+        let lower = sum * ln_front.exp();
+        (lower, 1.0 - lower)
+    } else {
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..=MAX_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+
+        let upper = ln_front.exp() * h;
+        (1.0 - upper, upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_chi2_cdf() {
+        // quantiles of the chi-square distribution
+        for (x, df, p) in [
+            (0.015790774093f64, 1, 0.10),
+            (3.841458820694, 1, 0.95),
+            (0.102586588775, 2, 0.05),
+            (9.210340371976, 2, 0.99),
+            (11.070497693516, 5, 0.95),
+            (1.145476226061, 5, 0.05),
+            (37.566234786625, 20, 0.99),
+        ] {
+            assert_relative_eq!(chi2_cdf(x, df), p, epsilon = 1e-9);
+        }
+
+        // with 2 degrees of freedom, P(X <= x) = 1 - exp(-x / 2)
+        for x in [0.5f64, 2.0, 7.5, 30.0] {
+            assert_relative_eq!(chi2_cdf(x, 2), 1.0 - (-x / 2.0).exp(), epsilon = 1e-12);
+        }
+
+        assert_relative_eq!(chi2_cdf(3.841_458_8_f32, 1), 0.95, epsilon = 1e-6);
+        assert_eq!(chi2_cdf(0.0f64, 3), 0.0);
+        assert_eq!(chi2_cdf(-1.0f64, 3), 0.0);
+        assert!(chi2_cdf(1.0f64, 0).is_nan());
+    }
+
+    #[test]
+    fn test_chi_square_survival() {
+        // quantiles of the chi-square distribution
+        assert_relative_eq!(
+            chi_square_survival(3.841458820694, 1.0),
+            0.05,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            chi_square_survival(9.210340371976, 2.0),
+            0.01,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            chi_square_survival(6.251388631170, 3.0),
+            0.10,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            chi_square_survival(18.307038053275, 10.0),
+            0.05,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(chi_square_survival(0.0, 4.0), 1.0, epsilon = 1e-12);
+    }
+}
@@ -12,9 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(any(feature = "unstable", test))]
+use nalgebra::{DVector, Scalar};
 use num_traits::Float;
+#[cfg(any(feature = "unstable", test))]
+use rand::Rng;
 
-use super::{AlphaLevel, Regression};
+use super::mackinnon::mackinnon_critical_value;
+use super::{AlphaLevel, CritValueSource, Regression};
+#[cfg(any(feature = "unstable", test))]
+use crate::tools::dickeyfuller::dickeyfuller_test;
+#[cfg(any(feature = "unstable", test))]
+use crate::utils::{gen_ar_1, quantile};
 use crate::Error;
 
 /// Approximate Dickey-Fuller distribution for specific alpha levels
@@ -26,14 +35,19 @@ pub fn constant_no_trend_critical_value<F: Float>(
     sz: usize,
     alpha: AlphaLevel,
 ) -> Result<F, crate::Error> {
-    let (t, u, v, w) = match alpha {
+    let (t, u, v, w) = constant_no_trend_coefficients(alpha);
+
+    calculate_t_stat_from_estimators(t, u, v, w, sz)
+}
+
+/// Response surface coefficients (t, u, v, w) of [`constant_no_trend_critical_value`].
+fn constant_no_trend_coefficients(alpha: AlphaLevel) -> (f64, f64, f64, f64) {
+    match alpha {
         AlphaLevel::OnePercent => (-3.43035, -6.5393, -16.786, -79.433),
         AlphaLevel::TwoPointFivePercent => (-3.1175, -4.53235, -9.8824, -57.7669),
         AlphaLevel::FivePercent => (-2.86154, -2.86154, -4.234, -40.04),
         AlphaLevel::TenPercent => (-2.56677, -1.5384, -2.809, 0.),
-    };
-
-    calculate_t_stat_from_estimators(t, u, v, w, sz)
+    }
 }
 
 /// Approximate Dickey-Fuller distribution for specific alpha levels
@@ -45,14 +59,19 @@ pub fn no_constant_no_trend_critical_value<F: Float>(
     sz: usize,
     alpha: AlphaLevel,
 ) -> Result<F, crate::Error> {
-    let (t, u, v, w) = match alpha {
+    let (t, u, v, w) = no_constant_no_trend_coefficients(alpha);
+
+    calculate_t_stat_from_estimators(t, u, v, w, sz)
+}
+
+/// Response surface coefficients (t, u, v, w) of [`no_constant_no_trend_critical_value`].
+fn no_constant_no_trend_coefficients(alpha: AlphaLevel) -> (f64, f64, f64, f64) {
+    match alpha {
         AlphaLevel::OnePercent => (-2.56574, -2.2358, -3.627, 0.),
         AlphaLevel::TwoPointFivePercent => (-2.222133, -1.15384, -3.4829, 17.17265),
         AlphaLevel::FivePercent => (-1.941, -0.2686, -3.365, 31.223),
         AlphaLevel::TenPercent => (-1.61682, 0.2656, -2.714, 25.364),
-    };
-
-    calculate_t_stat_from_estimators(t, u, v, w, sz)
+    }
 }
 
 /// Approximate Dickey-Fuller distribution for specific alpha levels
@@ -64,18 +83,49 @@ pub fn constant_trend_critical_value<F: Float>(
     sz: usize,
     alpha: AlphaLevel,
 ) -> Result<F, crate::Error> {
-    let (t, u, v, w) = match alpha {
+    let (t, u, v, w) = constant_trend_coefficients(alpha);
+
+    calculate_t_stat_from_estimators(t, u, v, w, sz)
+}
+
+/// Response surface coefficients (t, u, v, w) of [`constant_trend_critical_value`].
+fn constant_trend_coefficients(alpha: AlphaLevel) -> (f64, f64, f64, f64) {
+    match alpha {
         AlphaLevel::OnePercent => (-3.95877, -9.0531, -28.428, -134.155),
         AlphaLevel::TwoPointFivePercent => (-3.657216, -6.488615, -17.7624, -85.32545),
         AlphaLevel::FivePercent => (-3.41049, -4.3904, -9.036, -45.374),
         AlphaLevel::TenPercent => (-3.12705, -2.5856, -3.925, -22.38),
+    }
+}
+
+/// Returns the asymptotic critical value for a given regression and alpha level: the
+/// leading term t of the response surface t + u/n + v/n^2 + w/n^3, without the
+/// finite-sample correction.
+/// #Examples:
+/// ```rust
+/// use unit_root::prelude::distrib::dickeyfuller::asymptotic_critical_value;
+/// use unit_root::prelude::distrib::{AlphaLevel, Regression};
+/// let critical_value =
+///     asymptotic_critical_value::<f64>(Regression::Constant, AlphaLevel::FivePercent).unwrap();
+/// assert_eq!(critical_value, -2.86154);
+/// ```
+pub fn asymptotic_critical_value<F: Float>(
+    regression: Regression,
+    alpha: AlphaLevel,
+) -> Result<F, crate::Error> {
+    let (t, _u, _v, _w) = match regression {
+        Regression::Constant => constant_no_trend_coefficients(alpha),
+        Regression::ConstantAndTrend => constant_trend_coefficients(alpha),
+        Regression::NoConstantNoTrend => no_constant_no_trend_coefficients(alpha),
     };
 
-    calculate_t_stat_from_estimators(t, u, v, w, sz)
+    F::from(t).ok_or(Error::ConversionFailed)
 }
 
 /// Returns the critical value for a given regression and sample size
 /// for a given alpha level
+///
+/// Returns `Error::SampleSizeOutOfRange` if sz is below [`MIN_SAMPLE_SIZE`], i.e. 10.
 /// #Examples:
 /// ```rust
 /// use approx::assert_relative_eq;
@@ -99,13 +149,129 @@ pub fn get_critical_value<F: Float>(
     }
 }
 
-fn calculate_t_stat_from_estimators<F: Float>(
+/// Returns the critical value for a given regression and sample size
+/// for a given alpha level from the selected table.
+/// #Examples:
+/// ```rust
+/// use approx::assert_relative_eq;
+/// use unit_root::prelude::distrib::dickeyfuller::get_critical_value_from;
+/// use unit_root::prelude::distrib::{AlphaLevel, CritValueSource, Regression};
+/// let critical_value = get_critical_value_from::<f64>(
+///     CritValueSource::MacKinnon2010,
+///     Regression::Constant,
+///     10,
+///     AlphaLevel::FivePercent,
+/// );
+/// assert_relative_eq!(critical_value.unwrap(), -3.23295, epsilon = 1e-5);
+/// ```
+pub fn get_critical_value_from<F: Float>(
+    source: CritValueSource,
+    regression: Regression,
+    sz: usize,
+    alpha: AlphaLevel,
+) -> Result<F, crate::Error> {
+    match source {
+        CritValueSource::RealStatistics => get_critical_value(regression, sz, alpha),
+        CritValueSource::MacKinnon2010 => mackinnon_critical_value(regression, sz, alpha),
+    }
+}
+
+/// Returns the critical values for a given regression and alpha level
+/// for each of the requested sample sizes.
+/// #Examples:
+/// ```rust
+/// use unit_root::prelude::distrib::dickeyfuller::critical_value_curve;
+/// use unit_root::prelude::distrib::{AlphaLevel, Regression};
+/// let sizes = [25, 50, 100, 500];
+/// let curve =
+///     critical_value_curve::<f64>(Regression::Constant, AlphaLevel::FivePercent, &sizes).unwrap();
+/// assert_eq!(curve.len(), sizes.len());
+/// ```
+pub fn critical_value_curve<F: Float>(
+    regression: Regression,
+    alpha: AlphaLevel,
+    sizes: &[usize],
+) -> Result<Vec<F>, crate::Error> {
+    sizes
+        .iter()
+        .map(|&sz| get_critical_value(regression, sz, alpha))
+        .collect()
+}
+
+/// Returns n_sims statistics of [`dickeyfuller_test`] on Gaussian random walks: samples
+/// of the Dickey-Fuller null distribution, e.g. to overlay a histogram with an observed
+/// statistic or compute any of its quantiles.
+///
+/// The random walks have sz + 1 elements so that the test regressions have a sample size
+/// of sz, as the critical values of [`get_critical_value`].
+///
+/// Returns `Error::NotEnoughSamples` if sz is too small for the test regression.
+#[cfg(any(feature = "unstable", test))]
+pub fn simulate_null_distribution<R: Rng + ?Sized, F: Float + Scalar>(
+    rng: &mut R,
+    regression: Regression,
+    sz: usize,
+    n_sims: usize,
+) -> Result<DVector<F>, Error> {
+    let stats = (0..n_sims)
+        .map(|_| {
+            let y = gen_ar_1::<R, f64>(rng, sz + 1, 0.0, 1.0, 1.0);
+            let stat = dickeyfuller_test(&y, regression)?.test_statistic;
+            F::from(stat).ok_or(Error::ConversionFailed)
+        })
+        .collect::<Result<Vec<F>, Error>>()?;
+
+    Ok(DVector::from_vec(stats))
+}
+
+/// Dickey-Fuller critical value obtained by simulation: the empirical alpha quantile of
+/// the statistics of [`dickeyfuller_test`] on n_sims Gaussian random walks - see
+/// [`simulate_null_distribution`].
+///
+/// Unlike the response surfaces, any sz is supported - the precision is driven by
+/// n_sims.
+///
+/// Returns `Error::InvalidParameter` if n_sims is 0.
+#[cfg(any(feature = "unstable", test))]
+pub fn simulate_critical_value<R: Rng + ?Sized, F: Float>(
+    rng: &mut R,
+    regression: Regression,
+    sz: usize,
+    alpha: AlphaLevel,
+    n_sims: usize,
+) -> Result<F, crate::Error> {
+    if n_sims == 0 {
+        return Err(Error::InvalidParameter(
+            "the number of simulations must be positive".into(),
+        ));
+    }
+
+    let stats = simulate_null_distribution::<R, f64>(rng, regression, sz, n_sims)?;
+
+    F::from(quantile(&stats, alpha.value())?).ok_or(Error::ConversionFailed)
+}
+
+/// Smallest sample size for which the response surface of the critical values is used.
+/// Below, the 1/n^2 and 1/n^3 terms dominate and the polynomial is extrapolated into
+/// meaningless territory.
+pub const MIN_SAMPLE_SIZE: usize = 10;
+
+/// Evaluates the response surface t + u/n + v/n^2 + w/n^3 at n = sz.
+/// Returns `Error::SampleSizeOutOfRange` if sz is below [`MIN_SAMPLE_SIZE`].
+pub(crate) fn calculate_t_stat_from_estimators<F: Float>(
     t: f64,
     u: f64,
     v: f64,
     w: f64,
     sz: usize,
 ) -> Result<F, crate::Error> {
+    if sz < MIN_SAMPLE_SIZE {
+        return Err(Error::SampleSizeOutOfRange {
+            size: sz,
+            min: MIN_SAMPLE_SIZE,
+        });
+    }
+
     let n = sz as f64;
     let t_stat = t + (u / n) + (v / n.powi(2)) + (w / n.powi(3));
     let x = F::from(t_stat).ok_or(Error::ConversionFailed)?;
@@ -115,6 +281,8 @@ fn calculate_t_stat_from_estimators<F: Float>(
 #[cfg(test)]
 mod tests {
     use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
 
     use super::*;
 
@@ -267,4 +435,126 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_critical_value_curve() {
+        let sizes = [25, 100, 500];
+        let curve =
+            critical_value_curve::<f32>(Regression::Constant, AlphaLevel::OnePercent, &sizes)
+                .expect("failed to convert float");
+
+        assert_eq!(curve.len(), sizes.len());
+        for (cv, sz) in curve.iter().zip(sizes) {
+            assert_relative_eq!(
+                *cv,
+                get_critical_value::<f32>(Regression::Constant, sz, AlphaLevel::OnePercent)
+                    .unwrap()
+            );
+        }
+        assert_relative_eq!(curve[0], -3.724, epsilon = 1e-3);
+        assert_relative_eq!(curve[2], -3.443, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_critical_value_sample_size_out_of_range() {
+        for regression in [
+            Regression::NoConstantNoTrend,
+            Regression::Constant,
+            Regression::ConstantAndTrend,
+        ] {
+            let res = get_critical_value::<f64>(regression, 2, AlphaLevel::FivePercent);
+            assert!(matches!(
+                res,
+                Err(Error::SampleSizeOutOfRange { size: 2, min: 10 })
+            ));
+            assert!(get_critical_value::<f64>(
+                regression,
+                MIN_SAMPLE_SIZE,
+                AlphaLevel::FivePercent
+            )
+            .is_ok());
+        }
+
+        let cv: f64 =
+            get_critical_value(Regression::Constant, usize::MAX, AlphaLevel::FivePercent).unwrap();
+        assert_relative_eq!(cv, -2.86154, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_simulate_null_distribution() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let stats: DVector<f64> =
+            simulate_null_distribution(&mut rng, Regression::Constant, 100, 2000).unwrap();
+        assert_eq!(stats.len(), 2000);
+
+        // the Dickey-Fuller distribution with a constant is shifted to the left
+        let below = stats.iter().filter(|s| **s < -2.89).count() as f64;
+        assert_relative_eq!(below / 2000.0, 0.05, epsilon = 0.015);
+        assert!(stats.mean() < -1.0);
+
+        let res: Result<DVector<f64>, _> =
+            simulate_null_distribution(&mut rng, Regression::Constant, 1, 10);
+        assert!(matches!(res, Err(Error::NotEnoughSamples)));
+    }
+
+    #[test]
+    fn test_simulate_critical_value() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        for (regression, alpha) in [
+            (Regression::Constant, AlphaLevel::FivePercent),
+            (Regression::ConstantAndTrend, AlphaLevel::TenPercent),
+        ] {
+            let simulated: f64 =
+                simulate_critical_value(&mut rng, regression, 100, alpha, 2000).unwrap();
+            let tabulated: f64 = get_critical_value(regression, 100, alpha).unwrap();
+            assert_relative_eq!(simulated, tabulated, epsilon = 0.1);
+        }
+
+        // sizes below the response surface range are supported
+        let simulated: f64 = simulate_critical_value(
+            &mut rng,
+            Regression::Constant,
+            5,
+            AlphaLevel::FivePercent,
+            100,
+        )
+        .unwrap();
+        assert!(simulated < 0.0);
+
+        let res: Result<f64, _> = simulate_critical_value(
+            &mut rng,
+            Regression::Constant,
+            100,
+            AlphaLevel::FivePercent,
+            0,
+        );
+        assert!(matches!(res, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_asymptotic_critical_value() {
+        for regression in [
+            Regression::NoConstantNoTrend,
+            Regression::Constant,
+            Regression::ConstantAndTrend,
+        ] {
+            for alpha in [
+                AlphaLevel::OnePercent,
+                AlphaLevel::TwoPointFivePercent,
+                AlphaLevel::FivePercent,
+                AlphaLevel::TenPercent,
+            ] {
+                let asymptotic: f64 = asymptotic_critical_value(regression, alpha).unwrap();
+                let large_sample: f64 = get_critical_value(regression, 1_000_000, alpha).unwrap();
+                assert_relative_eq!(asymptotic, large_sample, epsilon = 1e-4);
+            }
+        }
+
+        let cv: f64 =
+            asymptotic_critical_value(Regression::ConstantAndTrend, AlphaLevel::OnePercent)
+                .unwrap();
+        assert_eq!(cv, -3.95877);
+    }
 }
@@ -0,0 +1,75 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Elliott-Rothenberg-Stock point-optimal test distribution.
+use num_traits::Float;
+
+use super::{AlphaLevel, Regression};
+use crate::Error;
+
+/// Critical values of the ERS point-optimal P_T statistic - the null hypothesis of a
+/// unit root is rejected when the statistic is below the critical value.
+///
+/// The table row is the one for the smallest tabulated sample size (50, 100, 200,
+/// infinity) at least as large as sz.
+/// Only the 1%, 5% and 10% levels are tabulated and `Regression::NoConstantNoTrend` is
+/// not supported.
+///
+/// Source: Elliott, Rothenberg and Stock (1996), Table 1.
+pub fn ers_critical_value<F: Float>(
+    regression: Regression,
+    sz: usize,
+    alpha: AlphaLevel,
+) -> Result<F, Error> {
+    // columns: 1%, 5%, 10% - rows: T = 50, 100, 200, infinity
+    let table: [[f64; 3]; 4] = match regression {
+        Regression::Constant => [
+            [1.87, 2.97, 3.91],
+            [1.95, 3.11, 4.17],
+            [1.91, 3.17, 4.33],
+            [1.99, 3.26, 4.48],
+        ],
+        Regression::ConstantAndTrend => [
+            [4.22, 5.72, 6.77],
+            [4.26, 5.64, 6.79],
+            [4.05, 5.66, 6.86],
+            [3.96, 5.62, 6.89],
+        ],
+        Regression::NoConstantNoTrend => {
+            return Err(Error::InvalidParameter(
+                "ERS critical values require a constant".into(),
+            ))
+        }
+    };
+
+    let column = match alpha {
+        AlphaLevel::OnePercent => 0,
+        AlphaLevel::FivePercent => 1,
+        AlphaLevel::TenPercent => 2,
+        AlphaLevel::TwoPointFivePercent => {
+            return Err(Error::InvalidParameter(
+                "ERS does not tabulate the 2.5% level".into(),
+            ))
+        }
+    };
+
+    let row = match sz {
+        0..=50 => 0,
+        51..=100 => 1,
+        101..=200 => 2,
+        _ => 3,
+    };
+
+    F::from(table[row][column]).ok_or(Error::ConversionFailed)
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! KPSS test distribution.
+use num_traits::Float;
+
+use super::{AlphaLevel, PValue, Regression};
+use crate::Error;
+
+/// Asymptotic critical values of the KPSS test statistic - the null hypothesis of
+/// stationarity is rejected when the statistic is above the critical value.
+/// - `Regression::Constant`: level stationarity
+/// - `Regression::ConstantAndTrend`: trend stationarity
+///
+/// `Regression::NoConstantNoTrend` is not supported.
+///
+/// Source: Kwiatkowski, Phillips, Schmidt and Shin (1992), Table 1.
+pub fn kpss_critical_value<F: Float>(
+    regression: Regression,
+    alpha: AlphaLevel,
+) -> Result<F, Error> {
+    let cv = match (regression, alpha) {
+        (Regression::Constant, AlphaLevel::OnePercent) => 0.739,
+        (Regression::Constant, AlphaLevel::TwoPointFivePercent) => 0.574,
+        (Regression::Constant, AlphaLevel::FivePercent) => 0.463,
+        (Regression::Constant, AlphaLevel::TenPercent) => 0.347,
+        (Regression::ConstantAndTrend, AlphaLevel::OnePercent) => 0.216,
+        (Regression::ConstantAndTrend, AlphaLevel::TwoPointFivePercent) => 0.176,
+        (Regression::ConstantAndTrend, AlphaLevel::FivePercent) => 0.146,
+        (Regression::ConstantAndTrend, AlphaLevel::TenPercent) => 0.119,
+        (Regression::NoConstantNoTrend, _) => {
+            return Err(Error::InvalidParameter(
+                "KPSS critical values require a constant".into(),
+            ))
+        }
+    };
+
+    F::from(cv).ok_or(Error::ConversionFailed)
+}
+
+/// Returns the p-value of the KPSS test statistic by linear interpolation within the
+/// tabulated critical values of [`kpss_critical_value`], like statsmodels.
+///
+/// Outside of the tabulated range the p-value is only known to be beyond the closest
+/// bound: `PValue::LessThan(0.01)` above the 1% critical value and
+/// `PValue::GreaterThan(0.10)` below the 10% critical value.
+///
+/// `Regression::NoConstantNoTrend` is not supported.
+///
+/// # Example
+/// ```
+/// use unit_root::prelude::distrib::kpss::kpss_p_value;
+/// use unit_root::prelude::distrib::{PValue, Regression};
+///
+/// let p_value = kpss_p_value(0.463f64, Regression::Constant).unwrap();
+/// assert_eq!(p_value, PValue::Value(0.05));
+///
+/// let p_value = kpss_p_value(1.2f64, Regression::Constant).unwrap();
+/// assert_eq!(p_value, PValue::LessThan(0.01));
+/// ```
+pub fn kpss_p_value<F: Float>(
+    test_statistic: F,
+    regression: Regression,
+) -> Result<PValue<F>, Error> {
+    let stat = test_statistic.to_f64().ok_or(Error::ConversionFailed)?;
+    if stat.is_nan() {
+        return Err(Error::InvalidParameter("test statistic is NaN".into()));
+    }
+
+    // (critical value, p-value) by increasing critical value
+    let table = [
+        (AlphaLevel::TenPercent, 0.10),
+        (AlphaLevel::FivePercent, 0.05),
+        (AlphaLevel::TwoPointFivePercent, 0.025),
+        (AlphaLevel::OnePercent, 0.01),
+    ]
+    .iter()
+    .map(|(alpha, p)| Ok((kpss_critical_value::<f64>(regression, *alpha)?, *p)))
+    .collect::<Result<Vec<(f64, f64)>, Error>>()?;
+
+    let to_f = |p: f64| F::from(p).ok_or(Error::ConversionFailed);
+
+    let (first, last) = (table[0], table[table.len() - 1]);
+    if stat < first.0 {
+        return Ok(PValue::GreaterThan(to_f(first.1)?));
+    }
+    if stat > last.0 {
+        return Ok(PValue::LessThan(to_f(last.1)?));
+    }
+
+    let p = table
+        .windows(2)
+        .find(|w| stat <= w[1].0)
+        .map(|w| {
+            let (x0, p0) = w[0];
+            let (x1, p1) = w[1];
+            p0 + (stat - x0) * (p1 - p0) / (x1 - x0)
+        })
+        .unwrap_or(last.1);
+
+    Ok(PValue::Value(to_f(p)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_kpss_p_value() {
+        // statsmodels.tsa.stattools.kpss interpolates the same table
+        let test_data = [
+            (Regression::Constant, 0.347, 0.10),
+            (Regression::Constant, 0.405, 0.075),
+            (Regression::Constant, 0.6, 0.022636363636),
+            (Regression::Constant, 0.739, 0.01),
+            (Regression::ConstantAndTrend, 0.13, 0.079629629630),
+            (Regression::ConstantAndTrend, 0.2, 0.016),
+        ];
+
+        for (regression, stat, expected) in test_data {
+            let p_value = kpss_p_value(stat, regression).unwrap();
+            assert!(!p_value.is_clamped());
+            assert_relative_eq!(p_value.value(), expected, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_kpss_p_value_out_of_range() {
+        assert_eq!(
+            kpss_p_value(0.1f64, Regression::Constant).unwrap(),
+            PValue::GreaterThan(0.10)
+        );
+        assert_eq!(
+            kpss_p_value(0.3f64, Regression::ConstantAndTrend).unwrap(),
+            PValue::LessThan(0.01)
+        );
+        assert!(kpss_p_value(0.3f64, Regression::NoConstantNoTrend).is_err());
+        assert!(kpss_p_value(f64::NAN, Regression::Constant).is_err());
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! MacKinnon approximate p-values and critical values for the Dickey-Fuller
+//! distribution.
+use num_traits::Float;
+
+use super::dickeyfuller::calculate_t_stat_from_estimators;
+use super::{normal_cdf, AlphaLevel, PValue, Regression};
+use crate::Error;
+
+/// Returns the critical value for a given regression and sample size for a given
+/// alpha level from the MacKinnon (2010) response surfaces for a single series - as
+/// statsmodels does.
+///
+/// Only the 1%, 5% and 10% levels are tabulated.
+///
+/// # References
+/// - MacKinnon, J.G. (2010). Critical Values for Cointegration Tests. Queen's Economics
+///   Department Working Paper No. 1227.
+/// - [Statsmodels](https://github.com/statsmodels/statsmodels/blob/main/statsmodels/tsa/adfvalues.py)
+pub fn mackinnon_critical_value<F: Float>(
+    regression: Regression,
+    sz: usize,
+    alpha: AlphaLevel,
+) -> Result<F, Error> {
+    let (t, u, v, w) = match (regression, alpha) {
+        (Regression::NoConstantNoTrend, AlphaLevel::OnePercent) => (-2.56574, -2.2358, -3.627, 0.),
+        (Regression::NoConstantNoTrend, AlphaLevel::FivePercent) => {
+            (-1.94100, -0.2686, -3.365, 31.223)
+        }
+        (Regression::NoConstantNoTrend, AlphaLevel::TenPercent) => {
+            (-1.61682, 0.2656, -2.714, 25.364)
+        }
+        (Regression::Constant, AlphaLevel::OnePercent) => (-3.43035, -6.5393, -16.786, -79.433),
+        (Regression::Constant, AlphaLevel::FivePercent) => (-2.86154, -2.8903, -4.234, -40.040),
+        (Regression::Constant, AlphaLevel::TenPercent) => (-2.56677, -1.5384, -2.809, 0.),
+        (Regression::ConstantAndTrend, AlphaLevel::OnePercent) => {
+            (-3.95877, -9.0531, -28.428, -134.155)
+        }
+        (Regression::ConstantAndTrend, AlphaLevel::FivePercent) => {
+            (-3.41049, -4.3904, -9.036, -45.374)
+        }
+        (Regression::ConstantAndTrend, AlphaLevel::TenPercent) => {
+            (-3.12705, -2.5856, -3.925, -22.380)
+        }
+        (_, AlphaLevel::TwoPointFivePercent) => {
+            return Err(Error::InvalidParameter(
+                "MacKinnon (2010) does not tabulate the 2.5% level".into(),
+            ))
+        }
+    };
+
+    calculate_t_stat_from_estimators(t, u, v, w, sz)
+}
+
+/// Smallest statistic for which the approximation was fitted.
+fn tau_min(regression: Regression) -> f64 {
+    match regression {
+        Regression::NoConstantNoTrend => -19.04,
+        Regression::Constant => -18.83,
+        Regression::ConstantAndTrend => -16.18,
+    }
+}
+
+/// Largest statistic for which the approximation was fitted.
+fn tau_max(regression: Regression) -> f64 {
+    match regression {
+        Regression::NoConstantNoTrend => f64::INFINITY,
+        Regression::Constant => 2.74,
+        Regression::ConstantAndTrend => 0.7,
+    }
+}
+
+/// Statistic at which the approximation switches from the small-p to the large-p
+/// polynomial.
+fn tau_star(regression: Regression) -> f64 {
+    match regression {
+        Regression::NoConstantNoTrend => -1.04,
+        Regression::Constant => -1.61,
+        Regression::ConstantAndTrend => -2.89,
+    }
+}
+
+/// Polynomial coefficients, in increasing order, used below tau_star.
+fn small_p_coefficients(regression: Regression) -> &'static [f64] {
+    match regression {
+        Regression::NoConstantNoTrend => &[0.6344, 1.2378, 3.2496e-2],
+        Regression::Constant => &[2.1659, 1.4412, 3.8269e-2],
+        Regression::ConstantAndTrend => &[3.2512, 1.6047, 4.9588e-2],
+    }
+}
+
+/// Polynomial coefficients, in increasing order, used above tau_star.
+fn large_p_coefficients(regression: Regression) -> &'static [f64] {
+    match regression {
+        Regression::NoConstantNoTrend => &[0.4797, 9.3557e-1, -0.6999e-1, 3.3066e-2],
+        Regression::Constant => &[1.7339, 9.3202e-1, -1.2745e-1, -1.0368e-2],
+        Regression::ConstantAndTrend => &[2.5261, 6.1654e-1, -3.7956e-1, -6.0285e-2],
+    }
+}
+
+fn p_value(tau: f64, regression: Regression) -> f64 {
+    polynomial_p_value(
+        tau,
+        tau_star(regression),
+        small_p_coefficients(regression),
+        large_p_coefficients(regression),
+    )
+}
+
+/// Evaluates the small-p polynomial up to tau_star and the large-p one above it, and
+/// maps the result through the normal CDF.
+fn polynomial_p_value(tau: f64, tau_star: f64, small_p: &[f64], large_p: &[f64]) -> f64 {
+    let coefficients = if tau <= tau_star { small_p } else { large_p };
+
+    let z = coefficients.iter().rev().fold(0., |acc, c| acc * tau + c);
+    normal_cdf(z)
+}
+
+/// Returns the p-value of the statistic, clamped to the region [min, max] over which
+/// the approximation was fitted.
+fn clamped_p_value<F: Float>(
+    test_statistic: F,
+    min: f64,
+    max: f64,
+    p_value: impl Fn(f64) -> f64,
+) -> Result<PValue<F>, Error> {
+    let tau = test_statistic.to_f64().ok_or(Error::ConversionFailed)?;
+    if tau.is_nan() {
+        return Err(Error::InvalidParameter("test statistic is NaN".into()));
+    }
+
+    let to_f = |p: f64| F::from(p).ok_or(Error::ConversionFailed);
+
+    if tau < min {
+        Ok(PValue::LessThan(to_f(p_value(min))?))
+    } else if tau > max {
+        Ok(PValue::GreaterThan(to_f(p_value(max))?))
+    } else {
+        Ok(PValue::Value(to_f(p_value(tau))?))
+    }
+}
+
+/// Returns the MacKinnon (1994) approximate p-value of a (Augmented) Dickey-Fuller
+/// test statistic for a single series.
+///
+/// Statistics outside of the region over which the approximation was fitted are not
+/// extrapolated: `PValue::LessThan` or `PValue::GreaterThan` is returned with the
+/// p-value at the boundary of that region.
+///
+/// # Examples:
+/// ```rust
+/// use unit_root::prelude::distrib::mackinnon::mackinnon_p_value;
+/// use unit_root::prelude::distrib::{PValue, Regression};
+///
+/// let p_value = mackinnon_p_value(-1.83288396527f64, Regression::Constant).unwrap();
+/// assert!(!p_value.is_clamped());
+/// assert!((p_value.value() - 0.364262207135).abs() < 1e-6);
+///
+/// let p_value = mackinnon_p_value(-25.0f64, Regression::Constant).unwrap();
+/// assert!(matches!(p_value, PValue::LessThan(_)));
+/// ```
+///
+/// # References
+/// - MacKinnon, J.G. (1994). Approximate asymptotic distribution functions for unit-root
+///   and cointegration tests. Journal of Business and Economic Statistics 12.
+/// - [Statsmodels](https://github.com/statsmodels/statsmodels/blob/main/statsmodels/tsa/adfvalues.py)
+pub fn mackinnon_p_value<F: Float>(
+    test_statistic: F,
+    regression: Regression,
+) -> Result<PValue<F>, Error> {
+    clamped_p_value(
+        test_statistic,
+        tau_min(regression),
+        tau_max(regression),
+        |tau| p_value(tau, regression),
+    )
+}
+
+/// Returns the MacKinnon (1994) approximate p-value of the Engle-Granger statistic of a
+/// pair of series: the Augmented Dickey-Fuller statistic of the residuals of the
+/// cointegrating regression with a constant - as statsmodels' coint.
+///
+/// Statistics outside of the region over which the approximation was fitted are not
+/// extrapolated, as with [`mackinnon_p_value`].
+///
+/// # References
+/// - MacKinnon, J.G. (1994). Approximate asymptotic distribution functions for unit-root
+///   and cointegration tests. Journal of Business and Economic Statistics 12.
+/// - [Statsmodels](https://github.com/statsmodels/statsmodels/blob/main/statsmodels/tsa/adfvalues.py)
+pub fn engle_granger_p_value<F: Float>(test_statistic: F) -> Result<PValue<F>, Error> {
+    clamped_p_value(test_statistic, -18.86, 0.92, |tau| {
+        polynomial_p_value(
+            tau,
+            -2.62,
+            &[2.92, 1.5012, 3.9796e-2],
+            &[2.1945, 6.4695e-1, -2.9198e-1, -4.2377e-2],
+        )
+    })
+}
+
+/// Expected p-values are from statsmodels.tsa.stattools.adfuller - see the tests of
+/// `tools::adf` and `tools::dickeyfuller`.
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_mackinnon_p_value() {
+        let test_data = [
+            // small p
+            (Regression::NoConstantNoTrend, -1.5140129055, 0.121977783883),
+            (Regression::Constant, -1.83288396527, 0.364262207135),
+            (
+                Regression::ConstantAndTrend,
+                -4.20337098854,
+                0.00442477220907,
+            ),
+            // large p
+            (
+                Regression::NoConstantNoTrend,
+                -0.417100483298,
+                0.529851882135,
+            ),
+            (Regression::Constant, 0.486121422662, 0.984445107564),
+        ];
+
+        for (regression, t_stat, expected) in test_data {
+            let p_value = mackinnon_p_value(t_stat, regression).unwrap();
+            assert!(!p_value.is_clamped());
+            assert_relative_eq!(p_value.value(), expected, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_mackinnon_p_value_out_of_range() {
+        let p_value = mackinnon_p_value(-30.0f64, Regression::Constant).unwrap();
+        assert!(matches!(p_value, PValue::LessThan(_)));
+        assert!(p_value.value() < 1e-10);
+
+        let p_value = mackinnon_p_value(3.0f64, Regression::Constant).unwrap();
+        assert!(matches!(p_value, PValue::GreaterThan(_)));
+        assert!(p_value.value() > 0.99);
+
+        let p_value = mackinnon_p_value(30.0f64, Regression::NoConstantNoTrend).unwrap();
+        assert!(matches!(p_value, PValue::Value(_)));
+
+        assert!(mackinnon_p_value(f64::NAN, Regression::Constant).is_err());
+    }
+
+    #[test]
+    fn test_engle_granger_p_value() {
+        // MacKinnon (2010) asymptotic critical values for two series with a constant
+        let test_data = [(-3.89644, 0.01), (-3.33613, 0.05), (-3.04445, 0.10)];
+
+        for (t_stat, expected) in test_data {
+            let p_value = engle_granger_p_value(t_stat).unwrap();
+            assert!(!p_value.is_clamped());
+            assert_relative_eq!(p_value.value(), expected, epsilon = 1e-4);
+        }
+
+        // the residuals are fitted: the statistic is more negative than for a single
+        // series at the same p-value
+        let single = mackinnon_p_value(-3.0f64, Regression::Constant).unwrap();
+        assert!(engle_granger_p_value(-3.0f64).unwrap().value() > single.value());
+
+        let p_value = engle_granger_p_value(1.0f64).unwrap();
+        assert!(matches!(p_value, PValue::GreaterThan(_)));
+        assert!(engle_granger_p_value(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_mackinnon_critical_value() {
+        // statsmodels critical values for 10 observations
+        let test_data = [
+            (
+                Regression::NoConstantNoTrend,
+                AlphaLevel::OnePercent,
+                -2.82559,
+            ),
+            (
+                Regression::NoConstantNoTrend,
+                AlphaLevel::FivePercent,
+                -1.970287,
+            ),
+            (
+                Regression::NoConstantNoTrend,
+                AlphaLevel::TenPercent,
+                -1.592036,
+            ),
+            (Regression::Constant, AlphaLevel::OnePercent, -4.331573),
+            (Regression::Constant, AlphaLevel::FivePercent, -3.23295),
+            (Regression::Constant, AlphaLevel::TenPercent, -2.7487),
+            (
+                Regression::ConstantAndTrend,
+                AlphaLevel::OnePercent,
+                -5.282515,
+            ),
+            (
+                Regression::ConstantAndTrend,
+                AlphaLevel::FivePercent,
+                -3.985264,
+            ),
+            (
+                Regression::ConstantAndTrend,
+                AlphaLevel::TenPercent,
+                -3.44724,
+            ),
+        ];
+
+        for (regression, alpha, expected) in test_data {
+            assert_relative_eq!(
+                mackinnon_critical_value::<f64>(regression, 10, alpha).unwrap(),
+                expected,
+                epsilon = 1e-6
+            );
+        }
+
+        assert!(mackinnon_critical_value::<f64>(
+            Regression::Constant,
+            10,
+            AlphaLevel::TwoPointFivePercent
+        )
+        .is_err());
+    }
+}
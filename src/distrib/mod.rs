@@ -11,7 +11,18 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+pub mod chi2;
 pub mod dickeyfuller;
+pub mod ers;
+pub mod kpss;
+pub mod mackinnon;
+pub mod normal;
+pub mod pp;
+
+use bitflags::bitflags;
+pub(crate) use chi2::chi_square_survival;
+pub(crate) use normal::{normal_cdf, normal_quantile};
+use num_traits::Float;
 
 /// Alpha levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,6 +37,40 @@ pub enum AlphaLevel {
     TenPercent,
 }
 
+impl AlphaLevel {
+    /// Returns all the alpha levels, from the most to the least stringent.
+    pub fn all() -> [AlphaLevel; 4] {
+        [
+            AlphaLevel::OnePercent,
+            AlphaLevel::TwoPointFivePercent,
+            AlphaLevel::FivePercent,
+            AlphaLevel::TenPercent,
+        ]
+    }
+
+    /// Returns the significance level as a probability, e.g. 0.05 for 5%.
+    pub fn value(&self) -> f64 {
+        match self {
+            AlphaLevel::OnePercent => 0.01,
+            AlphaLevel::TwoPointFivePercent => 0.025,
+            AlphaLevel::FivePercent => 0.05,
+            AlphaLevel::TenPercent => 0.10,
+        }
+    }
+}
+
+/// Source of the Dickey-Fuller critical values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CritValueSource {
+    /// Table from real-statistics.com - used by `dickeyfuller::get_critical_value`.
+    /// Provides 1%, 2.5%, 5% and 10% levels.
+    #[default]
+    RealStatistics,
+    /// MacKinnon (2010) response surfaces, as used by statsmodels.
+    /// Provides 1%, 5% and 10% levels.
+    MacKinnon2010,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Constant and trend parameters to include in regression.
 pub enum Regression {
@@ -36,3 +81,331 @@ pub enum Regression {
     /// no constant, no trend e.g. Δy_i = β_1*y_{i-1}  + ε_i
     NoConstantNoTrend,
 }
+
+bitflags! {
+    /// Deterministic terms of the test regression, to combine freely, e.g.
+    /// `Deterministics::CONST | Deterministics::TREND2`.
+    ///
+    /// The columns are added in the order of the flags: the constant, the trend t and
+    /// the quadratic trend t^2, with t = 1..n over the rows of the regression.
+    ///
+    /// Only the combinations of a [`Regression`] have tabulated critical values and
+    /// p-values - see [`Deterministics::regression`]:
+    /// - `Deterministics::empty()`: `Regression::NoConstantNoTrend`
+    /// - `Deterministics::CONST`: `Regression::Constant`
+    /// - `Deterministics::CONST | Deterministics::TREND`: `Regression::ConstantAndTrend`
+    ///
+    /// The statistic of any other combination follows a different distribution, e.g. to
+    /// be simulated.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Deterministics: u8 {
+        /// A constant
+        const CONST = 1;
+        /// A linear time trend
+        const TREND = 1 << 1;
+        /// A quadratic time trend
+        const TREND2 = 1 << 2;
+    }
+}
+
+impl Deterministics {
+    /// Returns the regression with the same deterministic terms, if any - i.e. whether
+    /// the Dickey-Fuller critical values of `distrib` apply.
+    pub fn regression(&self) -> Option<Regression> {
+        if *self == Deterministics::empty() {
+            Some(Regression::NoConstantNoTrend)
+        } else if *self == Deterministics::CONST {
+            Some(Regression::Constant)
+        } else if *self == Deterministics::CONST | Deterministics::TREND {
+            Some(Regression::ConstantAndTrend)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<Regression> for Deterministics {
+    fn from(regression: Regression) -> Self {
+        match regression {
+            Regression::NoConstantNoTrend => Deterministics::empty(),
+            Regression::Constant => Deterministics::CONST,
+            Regression::ConstantAndTrend => Deterministics::CONST | Deterministics::TREND,
+        }
+    }
+}
+
+/// A p-value obtained from an approximate distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PValue<F> {
+    /// The statistic lies within the fitted region of the approximation.
+    Value(F),
+    /// The statistic lies below the fitted region: the p-value is smaller than the
+    /// bound.
+    LessThan(F),
+    /// The statistic lies above the fitted region: the p-value is greater than the
+    /// bound.
+    GreaterThan(F),
+}
+
+impl<F: Copy> PValue<F> {
+    /// Returns the p-value or the bound it is known to be beyond.
+    pub fn value(&self) -> F {
+        match self {
+            PValue::Value(p) | PValue::LessThan(p) | PValue::GreaterThan(p) => *p,
+        }
+    }
+
+    /// Returns whether the statistic fell outside of the fitted region.
+    pub fn is_clamped(&self) -> bool {
+        !matches!(self, PValue::Value(_))
+    }
+}
+
+/// Strength of the evidence against the null hypothesis carried by a p-value - see
+/// [`PValue::evidence_level`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EvidenceLevel {
+    /// p >= 0.10
+    None,
+    /// 0.05 <= p < 0.10
+    Weak,
+    /// 0.01 <= p < 0.05
+    Strong,
+    /// p < 0.01
+    VeryStrong,
+}
+
+impl<F: Float> PValue<F> {
+    /// Returns the strength of the evidence against the null hypothesis, e.g. for reports
+    /// to non-statisticians.
+    ///
+    /// A clamped p-value gets the weakest level consistent with its bound: `LessThan(b)`
+    /// is classified as b itself would be if the thresholds were inclusive, and
+    /// `GreaterThan(_)` is always `EvidenceLevel::None`.
+    pub fn evidence_level(&self) -> EvidenceLevel {
+        let level = |p: F, strictly: bool| {
+            let below = |threshold: f64| {
+                let threshold = F::from(threshold).unwrap_or_else(F::zero);
+                p < threshold || (!strictly && p == threshold)
+            };
+            if below(0.01) {
+                EvidenceLevel::VeryStrong
+            } else if below(0.05) {
+                EvidenceLevel::Strong
+            } else if below(0.10) {
+                EvidenceLevel::Weak
+            } else {
+                EvidenceLevel::None
+            }
+        };
+
+        match *self {
+            PValue::Value(p) => level(p, true),
+            PValue::LessThan(bound) => level(bound, false),
+            PValue::GreaterThan(_) => EvidenceLevel::None,
+        }
+    }
+}
+
+/// Natural log of the gamma function, for x > 0.
+/// Uses the Lanczos approximation (g = 7, n = 9).
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |acc, (i, c)| {
+            acc + c / (x + (i + 1) as f64)
+        });
+
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// Regularized incomplete beta function I_x(a, b), for a, b > 0 and 0 <= x <= 1.
+/// Evaluates the continued fraction with the modified Lentz method.
+pub(crate) fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+
+    // the continued fraction converges rapidly for x < (a + 1) / (a + b + 2)
+    if x < (a + 1.0) / (a + b + 2.0) {
+        ln_front.exp() * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - ln_front.exp() * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 300;
+    const EPSILON: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+
+    let qab = a + b;
+    let qap = a + 1.0;
+    let qam = a - 1.0;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        // even step
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+
+        // odd step
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    h
+}
+
+/// Two-sided p-value of a Student's t statistic with df degrees of freedom:
+/// P(|T| > |t|).
+pub(crate) fn student_t_two_sided_p_value(t: f64, df: f64) -> f64 {
+    regularized_incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_evidence_level() {
+        assert_eq!(
+            PValue::Value(0.001).evidence_level(),
+            EvidenceLevel::VeryStrong
+        );
+        assert_eq!(PValue::Value(0.01).evidence_level(), EvidenceLevel::Strong);
+        assert_eq!(PValue::Value(0.049).evidence_level(), EvidenceLevel::Strong);
+        assert_eq!(PValue::Value(0.05).evidence_level(), EvidenceLevel::Weak);
+        assert_eq!(PValue::Value(0.10).evidence_level(), EvidenceLevel::None);
+        assert_eq!(PValue::Value(0.7f32).evidence_level(), EvidenceLevel::None);
+
+        assert_eq!(
+            PValue::LessThan(0.01).evidence_level(),
+            EvidenceLevel::VeryStrong
+        );
+        assert_eq!(
+            PValue::LessThan(0.03).evidence_level(),
+            EvidenceLevel::Strong
+        );
+        assert_eq!(
+            PValue::GreaterThan(0.001).evidence_level(),
+            EvidenceLevel::None
+        );
+
+        assert!(EvidenceLevel::VeryStrong > EvidenceLevel::Weak);
+    }
+
+    #[test]
+    fn test_deterministics() {
+        for regression in [
+            Regression::NoConstantNoTrend,
+            Regression::Constant,
+            Regression::ConstantAndTrend,
+        ] {
+            assert_eq!(
+                Deterministics::from(regression).regression(),
+                Some(regression)
+            );
+        }
+
+        assert_eq!(Deterministics::TREND.regression(), None);
+        assert_eq!(
+            (Deterministics::CONST | Deterministics::TREND | Deterministics::TREND2).regression(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_alpha_levels() {
+        let levels = AlphaLevel::all().map(|alpha| alpha.value());
+        assert_eq!(levels, [0.01, 0.025, 0.05, 0.10]);
+    }
+
+    #[test]
+    fn test_ln_gamma() {
+        assert_relative_eq!(ln_gamma(1.0), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(ln_gamma(5.0), 24f64.ln(), epsilon = 1e-12);
+        assert_relative_eq!(
+            ln_gamma(0.5),
+            std::f64::consts::PI.sqrt().ln(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_student_t_two_sided_p_value() {
+        // quantiles of the Student's t distribution
+        assert_relative_eq!(
+            student_t_two_sided_p_value(2.228138851986, 10.0),
+            0.05,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            student_t_two_sided_p_value(-4.032142983557, 5.0),
+            0.01,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(student_t_two_sided_p_value(0.0, 3.0), 1.0, epsilon = 1e-12);
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Standard normal distribution
+use num_traits::Float;
+
+/// Returns the standard normal cumulative distribution function at x: P(Z <= x).
+///
+/// The fractional error is below 1.2e-7 - see [`norm_ppf`] for the inverse.
+///
+/// # Examples:
+/// ```rust
+/// use unit_root::prelude::distrib::normal::norm_cdf;
+///
+/// assert!((norm_cdf(1.959963984540f64) - 0.975).abs() < 1e-7);
+/// ```
+pub fn norm_cdf<F: Float>(x: F) -> F {
+    let p = normal_cdf(x.to_f64().unwrap_or(f64::NAN));
+    F::from(p).unwrap_or_else(F::nan)
+}
+
+/// Returns the quantile function of the standard normal distribution at p, the inverse
+/// of [`norm_cdf`]: the z such that P(Z <= z) = p.
+///
+/// Uses Acklam's rational approximation with a relative error below 1.15e-9. Returns
+/// -inf at 0, +inf at 1 and NaN outside of [0, 1].
+pub fn norm_ppf<F: Float>(p: F) -> F {
+    let p = p.to_f64().unwrap_or(f64::NAN);
+    if !(0.0..=1.0).contains(&p) {
+        return F::nan();
+    }
+    F::from(normal_quantile(p)).unwrap_or_else(F::nan)
+}
+
+/// Standard normal cumulative distribution function.
+/// Uses the complementary error function approximation from Numerical Recipes with a
+/// fractional error below 1.2e-7.
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let erfc = t
+        * (-z * z - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409191
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+
+    if x >= 0.0 {
+        1.0 - 0.5 * erfc
+    } else {
+        0.5 * erfc
+    }
+}
+
+/// Quantile function of the standard normal distribution, the inverse of
+/// [`normal_cdf`]. Uses Acklam's rational approximation with a relative error below
+/// 1.15e-9. Returns -inf at 0 and +inf at 1.
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_norm_cdf() {
+        assert_relative_eq!(norm_cdf(0.0f64), 0.5, epsilon = 1e-7);
+        assert_relative_eq!(norm_cdf(1.0f64), 0.841344746069, epsilon = 1e-7);
+        assert_relative_eq!(norm_cdf(-2.326347874041f64), 0.01, epsilon = 1e-7);
+        assert_relative_eq!(norm_cdf(1.644_853_6_f32), 0.95, epsilon = 1e-6);
+        assert_eq!(norm_cdf(f64::INFINITY), 1.0);
+        assert_eq!(norm_cdf(f64::NEG_INFINITY), 0.0);
+    }
+
+    #[test]
+    fn test_norm_ppf() {
+        assert_relative_eq!(norm_ppf(0.95f32), 1.644_853_6, epsilon = 1e-6);
+        assert_relative_eq!(norm_ppf(0.841344746069f64), 1.0, epsilon = 1e-8);
+        assert!(norm_ppf(1.5f64).is_nan());
+        assert!(norm_ppf(-0.1f64).is_nan());
+
+        for p in [0.001f64, 0.2, 0.5, 0.7, 0.999] {
+            assert_relative_eq!(norm_cdf(norm_ppf(p)), p, epsilon = 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_normal_quantile() {
+        assert_relative_eq!(normal_quantile(0.5), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(normal_quantile(0.975), 1.959963984540, epsilon = 1e-8);
+        assert_relative_eq!(normal_quantile(0.01), -2.326347874041, epsilon = 1e-8);
+        assert_relative_eq!(normal_quantile(1e-10), -6.361340902404, epsilon = 1e-7);
+        assert_eq!(normal_quantile(0.0), f64::NEG_INFINITY);
+        assert_eq!(normal_quantile(1.0), f64::INFINITY);
+    }
+}
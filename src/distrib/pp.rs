@@ -0,0 +1,151 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Phillips-Perron test distributions.
+use num_traits::Float;
+
+use super::dickeyfuller::get_critical_value;
+use super::{AlphaLevel, Regression};
+use crate::Error;
+
+/// Phillips-Perron test statistic variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PpVariant {
+    /// Studentized statistic Z-tau - the usual choice, comparable to the (Augmented)
+    /// Dickey-Fuller t-statistic and follows the same distribution.
+    ZTau,
+    /// Normalized bias statistic Z-rho = n (rho - 1) corrected for serial correlation -
+    /// more powerful in some settings but more prone to size distortions with negative
+    /// moving-average errors.
+    ZRho,
+}
+
+/// Returns the critical value of a Phillips-Perron statistic for a given regression,
+/// sample size and alpha level. The null hypothesis of a unit root is rejected when the
+/// statistic is below the critical value.
+///
+/// - `PpVariant::ZTau` uses the Dickey-Fuller tau table - see
+///   `dickeyfuller::get_critical_value`.
+/// - `PpVariant::ZRho` uses the Dickey-Fuller normalized bias table: the row is the one
+///   for the smallest tabulated sample size (25, 50, 100, 250, 500, infinity) at least as
+///   large as sz.
+///
+/// Source for the normalized bias table: Fuller (1976), Table 8.5.1 - as reproduced in
+/// Hamilton (1994), Table B.5.
+pub fn pp_critical_value<F: Float>(
+    regression: Regression,
+    sz: usize,
+    alpha: AlphaLevel,
+    variant: PpVariant,
+) -> Result<F, Error> {
+    match variant {
+        PpVariant::ZTau => get_critical_value(regression, sz, alpha),
+        PpVariant::ZRho => z_rho_critical_value(regression, sz, alpha),
+    }
+}
+
+fn z_rho_critical_value<F: Float>(
+    regression: Regression,
+    sz: usize,
+    alpha: AlphaLevel,
+) -> Result<F, Error> {
+    // columns: 1%, 2.5%, 5%, 10% - rows: T = 25, 50, 100, 250, 500, infinity
+    let table: [[f64; 4]; 6] = match regression {
+        Regression::NoConstantNoTrend => [
+            [-11.9, -9.3, -7.3, -5.3],
+            [-12.9, -9.9, -7.7, -5.5],
+            [-13.3, -10.2, -7.9, -5.6],
+            [-13.6, -10.3, -8.0, -5.7],
+            [-13.7, -10.4, -8.0, -5.7],
+            [-13.8, -10.5, -8.1, -5.7],
+        ],
+        Regression::Constant => [
+            [-17.2, -14.6, -12.5, -10.2],
+            [-18.9, -15.7, -13.3, -10.7],
+            [-19.8, -16.3, -13.7, -11.0],
+            [-20.3, -16.6, -14.0, -11.2],
+            [-20.5, -16.8, -14.0, -11.2],
+            [-20.7, -16.9, -14.1, -11.3],
+        ],
+        Regression::ConstantAndTrend => [
+            [-22.5, -19.9, -17.9, -15.6],
+            [-25.7, -22.4, -19.8, -16.8],
+            [-27.4, -23.6, -20.7, -17.5],
+            [-28.4, -24.4, -21.3, -18.0],
+            [-28.9, -24.8, -21.5, -18.1],
+            [-29.5, -25.1, -21.8, -18.3],
+        ],
+    };
+
+    let column = match alpha {
+        AlphaLevel::OnePercent => 0,
+        AlphaLevel::TwoPointFivePercent => 1,
+        AlphaLevel::FivePercent => 2,
+        AlphaLevel::TenPercent => 3,
+    };
+
+    let row = match sz {
+        0..=25 => 0,
+        26..=50 => 1,
+        51..=100 => 2,
+        101..=250 => 3,
+        251..=500 => 4,
+        _ => 5,
+    };
+
+    F::from(table[row][column]).ok_or(Error::ConversionFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_pp_critical_value() {
+        let z_tau: f64 = pp_critical_value(
+            Regression::Constant,
+            25,
+            AlphaLevel::OnePercent,
+            PpVariant::ZTau,
+        )
+        .unwrap();
+        assert_relative_eq!(
+            z_tau,
+            get_critical_value::<f64>(Regression::Constant, 25, AlphaLevel::OnePercent).unwrap()
+        );
+
+        let test_data = [
+            (
+                Regression::NoConstantNoTrend,
+                25,
+                AlphaLevel::FivePercent,
+                -7.3,
+            ),
+            (Regression::Constant, 100, AlphaLevel::OnePercent, -19.8),
+            (Regression::Constant, 101, AlphaLevel::OnePercent, -20.3),
+            (
+                Regression::ConstantAndTrend,
+                10000,
+                AlphaLevel::TenPercent,
+                -18.3,
+            ),
+        ];
+        for (regression, sz, alpha, expected) in test_data {
+            let z_rho: f64 = pp_critical_value(regression, sz, alpha, PpVariant::ZRho).unwrap();
+            assert_relative_eq!(z_rho, expected);
+        }
+    }
+}
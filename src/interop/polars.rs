@@ -0,0 +1,137 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unit root tests on polars series
+use ::polars::prelude::{DataType, Series};
+use nalgebra::DVector;
+
+use crate::distrib::Regression;
+use crate::tools::adf::adf_test;
+use crate::tools::Report;
+use crate::Error;
+
+/// Handling of the null values of a polars series - see [`series_to_dvector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullPolicy {
+    /// Return `Error::InvalidParameter` if the series has a null value
+    #[default]
+    Error,
+    /// Drop the null values: the remaining observations are treated as consecutive,
+    /// e.g. for a few missing days of a daily series.
+    Drop,
+}
+
+/// Returns the values of a `Float64` polars series as a `DVector`, with its nulls
+/// handled according to nulls.
+///
+/// Returns `Error::InvalidParameter` naming the series if it is not of type `Float64`,
+/// or if it has a null value and nulls is `NullPolicy::Error`.
+pub fn series_to_dvector(series: &Series, nulls: NullPolicy) -> Result<DVector<f64>, Error> {
+    if series.dtype() != &DataType::Float64 {
+        return Err(Error::InvalidParameter(format!(
+            "series '{}' has type {}, expected f64",
+            series.name(),
+            series.dtype()
+        )));
+    }
+    let values = series
+        .f64()
+        .map_err(|e| Error::InvalidParameter(e.to_string()))?;
+
+    match nulls {
+        NullPolicy::Error => {
+            if let Some(i) = values.iter().position(|v| v.is_none()) {
+                return Err(Error::InvalidParameter(format!(
+                    "series '{}' has a null value at index {}",
+                    series.name(),
+                    i
+                )));
+            }
+            Ok(DVector::from_iterator(
+                values.len(),
+                values.iter().flatten(),
+            ))
+        }
+        NullPolicy::Drop => {
+            let values: Vec<f64> = values.iter().flatten().collect();
+            Ok(DVector::from_vec(values))
+        }
+    }
+}
+
+/// Augmented Dickey-Fuller test of a `Float64` polars series - see
+/// [`series_to_dvector`] for the handling of its nulls and `adf_test` for the test.
+pub fn adf_test_series(
+    series: &Series,
+    lag: usize,
+    regression: Regression,
+    nulls: NullPolicy,
+) -> Result<Report<f64>, Error> {
+    let y = series_to_dvector(series, nulls)?;
+    adf_test(&y, lag, regression)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::polars::prelude::{NamedFrom, Series};
+
+    use super::*;
+
+    const Y: [f64; 11] = [
+        -0.89642362,
+        0.3222552,
+        -1.96581989,
+        -1.10012936,
+        -1.3682928,
+        1.17239875,
+        2.19561259,
+        2.54295031,
+        2.05530587,
+        1.13212955,
+        -0.42968979,
+    ];
+
+    #[test]
+    fn test_adf_test_series() {
+        let series = Series::new("y".into(), &Y[..]);
+
+        let report = adf_test_series(&series, 0, Regression::Constant, NullPolicy::Error).unwrap();
+        let expected = adf_test(&DVector::from_row_slice(&Y[..]), 0, Regression::Constant).unwrap();
+        assert_eq!(report.test_statistic, expected.test_statistic);
+        assert_eq!(report.size, expected.size);
+    }
+
+    #[test]
+    fn test_adf_test_series_nulls() {
+        let mut values: Vec<Option<f64>> = Y.iter().copied().map(Some).collect();
+        values.insert(3, None);
+        let series = Series::new("y".into(), values);
+
+        let res = adf_test_series(&series, 0, Regression::Constant, NullPolicy::Error);
+        assert!(matches!(res, Err(Error::InvalidParameter(msg)) if msg.contains("'y'")));
+
+        let report = adf_test_series(&series, 0, Regression::Constant, NullPolicy::Drop).unwrap();
+        let expected = adf_test(&DVector::from_row_slice(&Y[..]), 0, Regression::Constant).unwrap();
+        assert_eq!(report.test_statistic, expected.test_statistic);
+    }
+
+    #[test]
+    fn test_series_to_dvector_dtype() {
+        let series = Series::new("n".into(), &[1i64, 2, 3]);
+        assert!(matches!(
+            series_to_dvector(&series, NullPolicy::Error),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+}
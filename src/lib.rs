@@ -35,15 +35,18 @@
 //!     2.05530587,
 //!     1.13212955,
 //!     -0.42968979,
+//!     0.13581245,
+//!     -0.95120654,
 //! ]);
 //!
+//! // the critical values need at least 10 observations after the lags are dropped
 //! let lag = 2;
 //! let regression = Regression::Constant;
 //! let report = adf_test(&y, lag, regression).unwrap();
 //!
 //! let critical_value: f64 =
 //!     get_critical_value(regression, report.size, AlphaLevel::OnePercent).unwrap();
-//! assert_eq!(report.size, 8);
+//! assert_eq!(report.size, 10);
 //!
 //! let t_stat = report.test_statistic;
 //! println!("t-statistic: {}", t_stat);
@@ -61,6 +64,8 @@
 use thiserror::Error;
 
 pub(crate) mod distrib;
+#[cfg(feature = "polars")]
+pub(crate) mod interop;
 pub(crate) mod tools;
 
 /// The public API.
@@ -89,4 +94,44 @@ pub enum Error {
     /// Failed to convert float.
     #[error("Failed to convert float")]
     ConversionFailed,
+    /// Invalid parameter.
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+    /// Log transform of a non-positive value.
+    #[error("Cannot take the log of non-positive value at index {0}")]
+    NonPositiveValue(usize),
+    /// The design matrix does not have full column rank, e.g. collinear columns.
+    #[error("Rank deficient design matrix: rank {rank} for {ncols} columns")]
+    RankDeficient {
+        /// The numerical rank of the design matrix
+        rank: usize,
+        /// The number of columns of the design matrix
+        ncols: usize,
+    },
+    /// The sample size is outside of the valid range of an approximation.
+    #[error("Sample size {size} is below the minimum of {min}")]
+    SampleSizeOutOfRange {
+        /// The sample size
+        size: usize,
+        /// The smallest supported sample size
+        min: usize,
+    },
+    /// The time index is not increasing by a constant step.
+    #[error("Irregular spacing of the time index at position {0}")]
+    IrregularSpacing(usize),
+    /// An iterative estimator hit its iteration cap without meeting its tolerance.
+    #[error("Not converged after {iterations} iterations")]
+    NotConverged {
+        /// The number of iterations performed
+        iterations: usize,
+    },
+    /// A test failed on a named series - see `TimeSeries::run`.
+    #[error("Series '{name}': {source}")]
+    Series {
+        /// The name of the series
+        name: String,
+        /// The error of the test
+        #[source]
+        source: Box<Error>,
+    },
 }
@@ -17,7 +17,7 @@
 
 /// Re-export what we need from nalgebra
 pub mod nalgebra {
-    pub use nalgebra::DVector;
+    pub use nalgebra::{DMatrix, DVector, SVector};
 }
 
 /// Errors
@@ -25,21 +25,103 @@ pub use crate::Error;
 
 /// Tools
 pub mod tools {
+    pub use crate::tools::adf::Adf;
     /// Augmented Dickey-Fuller test
-    pub use crate::tools::adf::adf_test;
+    pub use crate::tools::adf::{
+        adf_summary, adf_test, adf_test_all, adf_test_autolag, adf_test_columns,
+        adf_test_from_diff, adf_test_robust, adf_test_static, adf_test_transformed,
+        adf_test_verbose, adf_test_weighted, adf_test_with_deterministics, adf_test_with_residuals,
+        adf_test_with_terms, auto_integrate, compare_regressions, integration_order,
+        near_unit_root_test, random_walk_lr_test, rolling_adf, select_lag, AdfSummary,
+        AutoIntegrateReport, InfoCriterion, LagTieBreak, RegressionComparison,
+    };
+    /// Cointegration of pairs of series
+    pub use crate::tools::cointegration::{cointegration_matrix, spread_adf};
     /// Dickey-Fuller test
-    pub use crate::tools::dickeyfuller::dickeyfuller_test;
-    pub use crate::tools::Report;
+    pub use crate::tools::dickeyfuller::{
+        df_is_stationary, df_statistic, dickeyfuller_test, DickeyFuller,
+    };
+    /// Elliott-Rothenberg-Stock point-optimal test
+    pub use crate::tools::ers::{ers_point_optimal, Ers};
+    /// Tabular export of test reports
+    #[cfg(feature = "csv")]
+    pub use crate::tools::export::write_reports;
+    pub use crate::tools::export::ReportRow;
+    /// Kwiatkowski-Phillips-Schmidt-Shin stationarity test
+    pub use crate::tools::kpss::{kpss_test, Kpss};
+    /// Persistence of mean-reverting series
+    pub use crate::tools::meanrev::{
+        ar_root, estimated_ar_root, is_within_unit_circle, kendall_bias_correction,
+    };
+    /// Streaming Augmented Dickey-Fuller test
+    pub use crate::tools::online::OnlineAdf;
+    /// Panel unit root tests
+    pub use crate::tools::panel::{combine_p_values, CombineMethod};
+    /// Time series with metadata
+    pub use crate::tools::series::TimeSeries;
+    pub use crate::tools::{
+        coefficient_names, conclude, differenced_target, prepare_named, trend_stationarity_check,
+        Conclusion, PreparedDesign, Report, TestDecision, TestKind, Transform, TrendSpec,
+        TrendStationarityReport, UnitRootTest,
+    };
+
+    /// Time series diagnostics
+    pub mod diagnostics {
+        pub use crate::tools::diagnostics::{
+            acf, andrews_bandwidth, autocovariance, bds_test, cusum_test, long_run_variance,
+            newey_west_bandwidth, pacf, spectral_density_zero, Kernel,
+        };
+    }
+}
+
+/// Adapters for the data structures of other crates
+#[cfg(feature = "polars")]
+pub mod interop {
+    /// Unit root tests on polars series
+    pub use crate::interop::polars::{adf_test_series, series_to_dvector, NullPolicy};
 }
 
 /// Distributions
 pub mod distrib {
+    /// Chi-square distribution
+    pub mod chi2 {
+        pub use crate::distrib::chi2::chi2_cdf;
+    }
     /// Dickey-Fuller distribution
     pub mod dickeyfuller {
         pub use crate::distrib::dickeyfuller::{
-            constant_no_trend_critical_value, constant_trend_critical_value, get_critical_value,
-            no_constant_no_trend_critical_value,
+            asymptotic_critical_value, constant_no_trend_critical_value,
+            constant_trend_critical_value, critical_value_curve, get_critical_value,
+            get_critical_value_from, no_constant_no_trend_critical_value, MIN_SAMPLE_SIZE,
+        };
+        #[cfg(any(feature = "unstable", test))]
+        pub use crate::distrib::dickeyfuller::{
+            simulate_critical_value, simulate_null_distribution,
         };
     }
-    pub use crate::distrib::{AlphaLevel, Regression};
+    /// Elliott-Rothenberg-Stock point-optimal test distribution
+    pub mod ers {
+        pub use crate::distrib::ers::ers_critical_value;
+    }
+    /// KPSS distribution
+    pub mod kpss {
+        pub use crate::distrib::kpss::{kpss_critical_value, kpss_p_value};
+    }
+    /// MacKinnon approximate p-values
+    pub mod mackinnon {
+        pub use crate::distrib::mackinnon::{
+            engle_granger_p_value, mackinnon_critical_value, mackinnon_p_value,
+        };
+    }
+    /// Standard normal distribution
+    pub mod normal {
+        pub use crate::distrib::normal::{norm_cdf, norm_ppf};
+    }
+    /// Phillips-Perron test distributions
+    pub mod pp {
+        pub use crate::distrib::pp::{pp_critical_value, PpVariant};
+    }
+    pub use crate::distrib::{
+        AlphaLevel, CritValueSource, Deterministics, EvidenceLevel, PValue, Regression,
+    };
 }
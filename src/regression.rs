@@ -15,49 +15,603 @@
 use nalgebra::{DMatrix, DVector, RealField, Scalar};
 use num_traits::Float;
 
+use crate::distrib::{chi_square_survival, student_t_two_sided_p_value};
 use crate::prelude::Error;
+use crate::tools::{sorted, sorted_quantile};
+
+/// Divisor of the sum of squared residuals in the residual variance estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub enum Ddof {
+    /// Unbiased estimate: divide by n - k
+    #[default]
+    Residual,
+    /// Maximum likelihood estimate: divide by n
+    Mle,
+}
+
+impl Ddof {
+    fn divisor(&self, n: usize, k: usize) -> usize {
+        match self {
+            Ddof::Residual => n - k,
+            Ddof::Mle => n,
+        }
+    }
+}
+
+/// Options of the OLS regression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OlsOptions {
+    /// Divisor of the residual variance - n - k by default
+    pub ddof: Ddof,
+}
 
 /// Returns the beta coefficients and t-statistics of the OLS regression of y on x.
-/// Note: the intercept is the first coefficient.
+///
+/// Note: no intercept is added - x must include a constant column if one is wanted, as
+/// the Dickey-Fuller tests append for `Regression::Constant` and
+/// `Regression::ConstantAndTrend`. A second constant column makes x rank deficient - see
+/// [`has_intercept`].
+///
+/// Returns `Error::NotEnoughSamples` unless x has more rows than columns, as the residual
+/// variance needs at least one degree of freedom.
 pub fn ols<F: Float + Scalar + RealField>(
     y: &DVector<F>,
     x: &DMatrix<F>,
 ) -> Result<(DVector<F>, DVector<F>), Error> {
-    // Augment X with a column of 1s for the intercept - in first column
-    // let a = x.clone();
-    // number of observations (rows)
-    let n = x.nrows();
-    let k = x.ncols();
+    ols_with_options(y, x, OlsOptions::default())
+}
+
+/// Returns the beta coefficients and t-statistics of the OLS regression of y on x
+/// with the given options.
+///
+/// With `Ddof::Mle` the residual variance is divided by n instead of n - k: the standard
+/// errors are smaller and the t-statistics larger in absolute value by a factor
+/// sqrt(n / (n - k)). The Dickey-Fuller critical values assume `Ddof::Residual`, so the
+/// ADF comparison becomes more likely to reject in small samples.
+/// Note: as [`ols`], no intercept is added.
+pub fn ols_with_options<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+    options: OlsOptions,
+) -> Result<(DVector<F>, DVector<F>), Error> {
+    let result = ols_result(y, x, options)?;
+
+    Ok((result.betas, result.t_stats))
+}
+
+/// Result of an OLS regression - see [`ols_result`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OlsResult<F: Float + Scalar + RealField> {
+    /// The regression coefficients
+    pub betas: DVector<F>,
+    /// The t-statistics of the coefficients
+    pub t_stats: DVector<F>,
+    /// The variance-covariance matrix of the coefficients $\hat{\sigma}^2 (A'A)^{-1}$,
+    /// e.g. for joint confidence regions. The standard errors are the square roots of
+    /// its diagonal.
+    pub vcv: DMatrix<F>,
+    /// The 2-norm of the residuals $\|y - A\hat{\beta}\|$, e.g. for the convergence
+    /// check of iterative estimators.
+    pub residual_norm: F,
+}
+
+/// Same as [`ols_with_options`], also returning the variance-covariance matrix of the
+/// coefficients and the norm of the residuals.
+pub fn ols_result<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+    options: OlsOptions,
+) -> Result<OlsResult<F>, Error> {
+    // the residual variance needs at least one degree of freedom
+    if x.nrows() <= x.ncols() {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let (ata, aty) = normal_equations(y, x);
 
-    let at = &x.transpose();
     // beta = (A'A)^-1 A'y
-    let ata = at * x;
-    let ata_inv = &ata
+    let ata_inv = ata
         .try_inverse()
-        .ok_or_else(|| Error::FailedToInvertMatrix("OLS failed to invert A.T*A".into()))?;
-    let aty = at * y;
+        .ok_or_else(|| inversion_error(x, "OLS failed to invert A.T*A"))?;
 
     // the regression coefficients
-    let beta_ = ata_inv * aty;
+    let betas = &ata_inv * aty;
+
+    let rtr = residuals(y, x, &betas).norm_squared();
+    let vcv = covariance_from_ssr(rtr, x, &ata_inv, options.ddof);
+    let t_stats = t_statistics_from_covariance(&betas, &vcv);
+
+    Ok(OlsResult {
+        betas,
+        t_stats,
+        vcv,
+        residual_norm: Float::sqrt(rtr),
+    })
+}
+
+/// Returns the beta coefficients and t-statistics of the OLS regression of y on x.
+/// The normal equations are solved with a Cholesky decomposition of A'A instead of
+/// an explicit inverse. This requires x to have full column rank.
+/// Note: as [`ols`], no intercept is added.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub fn ols_cholesky<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+) -> Result<(DVector<F>, DVector<F>), Error> {
+    // the residual variance needs at least one degree of freedom
+    if x.nrows() <= x.ncols() {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let (ata, aty) = normal_equations(y, x);
+
+    // A'A = L L'
+    let chol = ata.cholesky().ok_or_else(|| {
+        inversion_error(
+            x,
+            "OLS failed to compute the Cholesky decomposition of A.T*A",
+        )
+    })?;
+
+    // the regression coefficients
+    let beta_ = chol.solve(&aty);
+
+    let t_statistics = t_statistics(y, x, &beta_, &chol.inverse(), Ddof::Residual);
+
+    Ok((beta_, t_statistics))
+}
+
+/// OLS regression on a fixed design matrix x, with (A'A)^-1 computed once and reused
+/// for every y.
+#[derive(Debug, Clone)]
+pub struct OlsPrecomputed<F: Float + Scalar + RealField> {
+    x: DMatrix<F>,
+    at: DMatrix<F>,
+    ata_inv: DMatrix<F>,
+}
+
+/// Precomputes the factorization of the design matrix x for repeated OLS regressions
+/// with [`OlsPrecomputed::solve`].
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub fn precompute<F: Float + Scalar + RealField>(
+    x: &DMatrix<F>,
+) -> Result<OlsPrecomputed<F>, Error> {
+    // the residual variance needs at least one degree of freedom
+    if x.nrows() <= x.ncols() {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let at = x.transpose();
+    let ata = &at * x;
+    let ata_inv = ata
+        .try_inverse()
+        .ok_or_else(|| inversion_error(x, "OLS failed to invert A.T*A"))?;
+
+    Ok(OlsPrecomputed {
+        x: x.clone(),
+        at,
+        ata_inv,
+    })
+}
+
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+impl<F: Float + Scalar + RealField> OlsPrecomputed<F> {
+    /// Returns the beta coefficients and t-statistics of the OLS regression of y on the
+    /// precomputed design matrix.
+    /// y must have as many rows as the design matrix.
+    pub fn solve(&self, y: &DVector<F>) -> (DVector<F>, DVector<F>) {
+        let aty = &self.at * y;
+
+        // the regression coefficients
+        let beta_ = &self.ata_inv * aty;
+
+        let t_statistics = t_statistics(y, &self.x, &beta_, &self.ata_inv, Ddof::Residual);
+
+        (beta_, t_statistics)
+    }
+}
+
+/// Wald test of the linear hypothesis R beta = r on a fitted OLS regression - see
+/// [`ols_result`], which requires more rows than columns.
+///
+/// Returns the Wald statistic
+/// $W = (R\hat{\beta} - r)' [R \hat{V} R']^{-1} (R\hat{\beta} - r)$
+/// where $\hat{V}$ is the covariance matrix of the coefficients of the result, and its
+/// F approximation $W / q$ with q the number of restrictions (rows of R).
+///
+/// E.g. to test that the coefficients of the last two lagged differences of an ADF
+/// regression are jointly zero, R selects their columns and r is zero.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub fn wald_test<F: Float + Scalar + RealField>(
+    ols_result: &OlsResult<F>,
+    restriction: &DMatrix<F>,
+    r: &DVector<F>,
+) -> Result<(F, F), Error> {
+    let q = restriction.nrows();
+    let k = ols_result.betas.len();
+    if q == 0 || restriction.ncols() != k || r.len() != q {
+        return Err(Error::InvalidParameter(format!(
+            "restriction must be q x {} and r of length q, got {} x {} and {}",
+            k,
+            q,
+            restriction.ncols(),
+            r.len()
+        )));
+    }
+
+    let diff = restriction * &ols_result.betas - r;
+    let middle = (restriction * &ols_result.vcv * restriction.transpose())
+        .try_inverse()
+        .ok_or_else(|| Error::FailedToInvertMatrix("Wald test failed to invert R V R.T".into()))?;
+
+    let wald = diff.dot(&(middle * &diff));
+    let f_stat = wald / F::from(q).ok_or(Error::ConversionFailed)?;
+
+    Ok((wald, f_stat))
+}
+
+/// Returns the beta coefficients and t-statistics of the weighted least squares
+/// regression of y on x, minimizing $\sum_t w_t (y_t - x_t \beta)^2$, e.g. with
+/// $w_t = 1 / \sigma_t^2$ for heteroskedastic observations of known volatility.
+///
+/// This is the OLS regression of the rows scaled by $\sqrt{w_t}$ - the t-statistics are
+/// those of the transformed regression. Only the relative weights matter.
+///
+/// Returns `Error::InvalidParameter` if weights does not have one non-negative weight
+/// per row. Note: as [`ols`], no intercept is added.
+pub fn wls<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+    weights: &DVector<F>,
+) -> Result<(DVector<F>, DVector<F>), Error> {
+    if weights.len() != y.len() {
+        return Err(Error::InvalidParameter(format!(
+            "weights has length {} but y has length {}",
+            weights.len(),
+            y.len()
+        )));
+    }
+    if let Some(i) = weights.iter().position(|w| w.is_nan() || *w < F::zero()) {
+        return Err(Error::InvalidParameter(format!(
+            "weight {} is not a non-negative number",
+            i
+        )));
+    }
+
+    let sqrt_w = weights.map(Float::sqrt);
+
+    let mut x_w = x.clone();
+    for (mut row, w) in x_w.row_iter_mut().zip(sqrt_w.iter()) {
+        row *= *w;
+    }
+    let y_w = y.component_mul(&sqrt_w);
+
+    ols(&y_w, &x_w)
+}
+
+/// Returns the beta coefficients and t-statistics of a robust regression of y on x with
+/// the Huber loss, by iteratively reweighted least squares.
+///
+/// Starting from the OLS fit, each iteration scales the residuals by their median
+/// absolute deviation / 0.6745 and reweights the observations with scaled residual u by
+/// min(1, tuning / |u|) - 1.345 gives 95% efficiency under normal errors. The iterations
+/// stop once the coefficients no longer move, or once the residuals have no spread left
+/// to scale by.
+///
+/// Returns `Error::NotConverged` if the coefficients still move after max_iter
+/// iterations. With max_iter = 0 no reweighting is requested and this is the OLS fit.
+///
+/// The t-statistics are those of the final weighted least squares fit.
+/// Note: as [`ols`], no intercept is added.
+pub fn robust_regression<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+    tuning: F,
+    max_iter: usize,
+) -> Result<(DVector<F>, DVector<F>), Error> {
+    if Float::is_nan(tuning) || tuning <= F::zero() {
+        return Err(Error::InvalidParameter(
+            "the Huber tuning constant must be positive".into(),
+        ));
+    }
+
+    let tolerance = F::from(1e-10).ok_or(Error::ConversionFailed)?;
+    let mad_scale = F::from(0.6745).ok_or(Error::ConversionFailed)?;
+
+    let (mut beta_, mut t_stats) = ols(y, x)?;
+    if max_iter == 0 {
+        return Ok((beta_, t_stats));
+    }
+
+    for _ in 0..max_iter {
+        let r = residuals(y, x, &beta_);
+        let center = median(&r);
+        let scale = median(&r.map(|v| Float::abs(v - center))) / mad_scale;
+        if Float::is_nan(scale) || scale <= F::zero() {
+            return Ok((beta_, t_stats));
+        }
+
+        // the Huber weights of the rows of the regression
+        let weights = r.map(|v| {
+            let u = Float::abs(v / scale);
+            if u <= tuning {
+                F::one()
+            } else {
+                tuning / u
+            }
+        });
+
+        let (new_beta, new_t_stats) = wls(y, x, &weights)?;
+        let change = (&new_beta - &beta_).amax();
+        beta_ = new_beta;
+        t_stats = new_t_stats;
+
+        if change <= tolerance * (F::one() + beta_.amax()) {
+            return Ok((beta_, t_stats));
+        }
+    }
+
+    Err(Error::NotConverged {
+        iterations: max_iter,
+    })
+}
+
+/// Returns the median of v: its 0.5 quantile - see `utils::quantile`.
+fn median<F: Float + Scalar + RealField>(v: &DVector<F>) -> F {
+    sorted_quantile(&sorted(v.as_slice()), F::from(0.5).unwrap_or_else(F::nan))
+}
+
+/// Returns the maximized Gaussian log-likelihood of an OLS regression with the given
+/// sum of squared residuals and number of observations.
+fn log_likelihood<F: Float>(ssr: F, nobs: usize) -> F {
+    let nobs = F::from(nobs).unwrap();
+    let two_pi = F::from(2.0 * std::f64::consts::PI).unwrap();
+
+    -nobs / F::from(2.0).unwrap() * (two_pi.ln() + (ssr / nobs).ln() + F::one())
+}
+
+/// Akaike information criterion of an OLS regression with k parameters:
+/// $-2 \ln L + 2k$ where $\ln L$ is the Gaussian log-likelihood, as in statsmodels.
+pub fn aic<F: Float>(ssr: F, nobs: usize, k: usize) -> F {
+    F::from(-2.0).unwrap() * log_likelihood(ssr, nobs) + F::from(2 * k).unwrap()
+}
+
+/// Bayesian information criterion of an OLS regression with k parameters:
+/// $-2 \ln L + k \ln(n)$ where $\ln L$ is the Gaussian log-likelihood, as in
+/// statsmodels.
+pub fn bic<F: Float>(ssr: F, nobs: usize, k: usize) -> F {
+    F::from(-2.0).unwrap() * log_likelihood(ssr, nobs)
+        + F::from(k).unwrap() * F::from(nobs).unwrap().ln()
+}
+
+/// Likelihood-ratio test of a restricted OLS regression nested in an unrestricted one
+/// with df more parameters - e.g. `Regression::Constant` against
+/// `Regression::ConstantAndTrend` on the same sample, with df = 1.
+///
+/// Returns the statistic $n \ln(SSR_r / SSR_u)$, twice the difference of the Gaussian
+/// log-likelihoods, and its p-value under the chi-square distribution with df degrees
+/// of freedom.
+///
+/// Note: both regressions must be fitted on the same nobs observations. With a unit root
+/// in the regressors the statistic is not chi-square distributed, e.g. for the trend of
+/// a Dickey-Fuller regression under the null, and the p-value is only indicative.
+///
+/// The p-value is NaN if df is 0: the regressions are not nested with a restriction.
+pub fn lr_test<F: Float>(ssr_restricted: F, ssr_unrestricted: F, nobs: usize, df: usize) -> (F, F) {
+    let statistic = F::from(2.0).unwrap()
+        * (log_likelihood(ssr_unrestricted, nobs) - log_likelihood(ssr_restricted, nobs));
+    if df == 0 {
+        return (statistic, F::nan());
+    }
+
+    let p_value = chi_square_survival(statistic.to_f64().unwrap_or(f64::NAN), df as f64);
+
+    (statistic, F::from(p_value).unwrap_or_else(F::nan))
+}
+
+/// Returns the two-sided p-values of the t-statistics of an OLS regression using
+/// Student's t distribution with df_resid = n - k degrees of freedom.
+///
+/// This is valid for the augmenting lag coefficients of a (Augmented) Dickey-Fuller
+/// regression but NOT for the level term: under the unit-root null its t-statistic
+/// follows the Dickey-Fuller distribution - see `distrib::dickeyfuller`.
+pub fn coef_p_values<F: Float + Scalar + RealField>(
+    t_stats: &DVector<F>,
+    df_resid: usize,
+) -> Result<DVector<F>, Error> {
+    if df_resid == 0 {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let df = df_resid as f64;
+    t_stats
+        .iter()
+        .map(|t| {
+            let t = t.to_f64().ok_or(Error::ConversionFailed)?;
+            F::from(student_t_two_sided_p_value(t, df)).ok_or(Error::ConversionFailed)
+        })
+        .collect::<Result<Vec<F>, Error>>()
+        .map(DVector::from_vec)
+}
 
+// Linear algebra shared by the OLS solvers - only the solve step differs between them.
+
+/// Returns the numerical rank of x from its column-pivoted QR decomposition: the number
+/// of diagonal elements of R above max(n, k) * eps * the largest of them in absolute
+/// value, the QR analog of numpy.linalg.matrix_rank's threshold.
+fn matrix_rank<F: Float + Scalar + RealField>(x: &DMatrix<F>) -> usize {
+    if x.is_empty() {
+        return 0;
+    }
+
+    // R is min(n, k) x k
+    let r = x.clone().col_piv_qr().r();
+    let r_diagonal: Vec<F> = (0..r.nrows()).map(|i| Float::abs(r[(i, i)])).collect();
+
+    let max = r_diagonal
+        .iter()
+        .fold(F::zero(), |acc, v| Float::max(acc, *v));
+    let scale = F::from(x.nrows().max(x.ncols())).unwrap();
+    let tolerance = max * scale * Float::epsilon();
+    r_diagonal.iter().filter(|v| **v > tolerance).count()
+}
+
+/// Returns whether x has a column of ones, i.e. an intercept - see [`ols`].
+///
+/// Other constant columns are not reported: with few rows, they may be regressors that
+/// happen to be constant.
+pub fn has_intercept<F: Float + Scalar + RealField>(x: &DMatrix<F>) -> bool {
+    x.nrows() > 0
+        && x.column_iter()
+            .any(|column| column.iter().all(|v| *v == F::one()))
+}
+
+/// Returns `Error::RankDeficient` with the numerical rank if x does not have full
+/// column rank, e.g. to validate user-supplied regressors before the solve rather than
+/// getting an inversion failure from [`ols`].
+///
+/// The rank is revealed by a column-pivoted QR decomposition.
+pub fn check_full_rank<F: Float + Scalar + RealField>(x: &DMatrix<F>) -> Result<(), Error> {
+    let rank = matrix_rank(x);
+    if rank < x.ncols() {
+        return Err(Error::RankDeficient {
+            rank,
+            ncols: x.ncols(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the externally studentized residuals of the OLS regression of y on x, e.g.
+/// to spot the observations distorting a Dickey-Fuller regression.
+///
+/// Each residual is scaled by the standard error of the regression without its
+/// observation: $t_i = e_i / (s_{(i)} \sqrt{1 - h_{ii}})$ with the leverage
+/// $h_{ii} = x_i (A'A)^{-1} x_i'$ and
+/// $s_{(i)}^2 = (SSR - e_i^2 / (1 - h_{ii})) / (n - k - 1)$.
+/// Under the model assumptions $t_i$ follows Student's t distribution with n - k - 1
+/// degrees of freedom. It is not finite for an observation with leverage 1.
+///
+/// Note: as [`ols`], no intercept is added.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub fn studentized_residuals<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+) -> Result<DVector<F>, Error> {
+    // the leave-one-out residual variance needs at least one degree of freedom
+    if x.nrows() <= x.ncols() + 1 {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let (ata, aty) = normal_equations(y, x);
+    let ata_inv = ata
+        .try_inverse()
+        .ok_or_else(|| inversion_error(x, "OLS failed to invert A.T*A"))?;
+    let betas = &ata_inv * aty;
+
+    let resids = residuals(y, x, &betas);
+    let ssr = resids.norm_squared();
+    let df = F::from(x.nrows() - x.ncols() - 1).ok_or(Error::ConversionFailed)?;
+
+    let studentized = x
+        .row_iter()
+        .zip(resids.iter())
+        .map(|(row, e)| {
+            let leverage = (row * &ata_inv).dot(&row);
+            let one_minus_h = F::one() - leverage;
+            let s2 = (ssr - *e * *e / one_minus_h) / df;
+            *e / Float::sqrt(s2 * one_minus_h)
+        })
+        .collect::<Vec<F>>();
+
+    Ok(DVector::from_vec(studentized))
+}
+
+/// Returns the error to report when A'A cannot be inverted: `Error::RankDeficient` if x
+/// does not have full column rank, `Error::FailedToInvertMatrix` otherwise.
+fn inversion_error<F: Float + Scalar + RealField>(x: &DMatrix<F>, msg: &str) -> Error {
+    match check_full_rank(x) {
+        Err(e) => e,
+        Ok(()) => Error::FailedToInvertMatrix(msg.into()),
+    }
+}
+
+/// Returns the normal equations (A'A, A'y) of the regression of y on x.
+fn normal_equations<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+) -> (DMatrix<F>, DVector<F>) {
+    let at = x.transpose();
+    let ata = &at * x;
+    let aty = &at * y;
+    (ata, aty)
+}
+
+/// Returns the residuals y - x * beta.
+pub(crate) fn residuals<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+    beta: &DVector<F>,
+) -> DVector<F> {
     // the predicted values
-    let y_hat = x * &beta_;
+    let y_hat = x * beta;
+
+    y - y_hat
+}
 
-    // the residuals
-    let residuals = y - y_hat;
+/// Returns the covariance matrix of the coefficients beta given (A'A)^-1.
+fn covariance<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+    beta: &DVector<F>,
+    ata_inv: &DMatrix<F>,
+    ddof: Ddof,
+) -> DMatrix<F> {
+    let residuals = residuals(y, x, beta);
+    let rtr = residuals.dot(&residuals);
 
-    let rtr = &residuals.transpose() * &residuals;
-    let rtr = rtr.get((0, 0)).unwrap();
+    covariance_from_ssr(rtr, x, ata_inv, ddof)
+}
+
+/// Returns the covariance matrix of the coefficients given the sum of squared residuals
+/// rtr and (A'A)^-1.
+fn covariance_from_ssr<F: Float + Scalar + RealField>(
+    rtr: F,
+    x: &DMatrix<F>,
+    ata_inv: &DMatrix<F>,
+    ddof: Ddof,
+) -> DMatrix<F> {
+    // number of observations (rows)
+    let n = x.nrows();
+    let k = x.ncols();
 
     // The variance of the residuals
-    let vcv = ata_inv * (*rtr / F::from(n - k).unwrap());
+    ata_inv * (rtr / F::from(ddof.divisor(n, k)).unwrap())
+}
+
+/// Returns the t-statistics of the coefficients beta given (A'A)^-1.
+fn t_statistics<F: Float + Scalar + RealField>(
+    y: &DVector<F>,
+    x: &DMatrix<F>,
+    beta: &DVector<F>,
+    ata_inv: &DMatrix<F>,
+    ddof: Ddof,
+) -> DVector<F> {
+    let vcv = covariance(y, x, beta, ata_inv, ddof);
 
+    t_statistics_from_covariance(beta, &vcv)
+}
+
+/// Returns the t-statistics of the coefficients beta given their covariance matrix.
+fn t_statistics_from_covariance<F: Float + Scalar + RealField>(
+    beta: &DVector<F>,
+    vcv: &DMatrix<F>,
+) -> DVector<F> {
     // The standard errors of the coefficients
     let se = vcv.diagonal().map(|x| Float::sqrt(x));
 
-    let t_statistics = beta_.component_div(&se);
-
-    Ok((beta_, t_statistics))
+    beta.component_div(&se)
 }
 
 #[cfg(test)]
@@ -68,7 +622,9 @@ mod tests {
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
 
-    use crate::utils::{gen_affine_data, gen_affine_data_with_whitenoise};
+    use crate::utils::{
+        gen_affine_data, gen_affine_data_with_noise, gen_affine_data_with_whitenoise, gen_ar_1,
+    };
     use crate::Error;
 
     fn add_constant<F: Float + Scalar + RealField>(x: &mut DMatrix<F>) {
@@ -77,8 +633,26 @@ mod tests {
         x.extend(a)
     }
 
+    #[test]
+    fn test_has_intercept() {
+        let mut x = DMatrix::from_row_slice(3, 1, &[1.0f64, 2.0, 3.0]);
+        assert!(!super::has_intercept(&x));
+
+        x.extend(vec![0.0; 3]);
+        assert!(!super::has_intercept(&x));
+
+        x.extend(vec![2.0; 3]);
+        assert!(!super::has_intercept(&x));
+
+        add_constant(&mut x);
+        assert!(super::has_intercept(&x));
+
+        assert!(!super::has_intercept(&DMatrix::<f64>::zeros(0, 2)));
+    }
+
     #[test]
     fn test_ols_f32() {
+        // an exact fit: the residual variance is 0 and the t-statistics degenerate
         let y = DVector::from_row_slice(&[1.0f32, 2.0, 3.0, 4.0, 5.0]);
         let mut x = DMatrix::from_row_slice(5, 1, &[1.0f32, 2.0, 3.0, 4.0, 5.0]);
         add_constant(&mut x);
@@ -93,6 +667,7 @@ mod tests {
 
     #[test]
     fn test_ols_f64() {
+        // an exact fit: the residual variance is 0 and the t-statistics degenerate
         let y = DVector::from_row_slice(&[1.0f64, 2.0, 3.0, 4.0, 5.0]);
         let mut x = DMatrix::from_row_slice(5, 1, &[1.0f64, 2.0, 3.0, 4.0, 5.0]);
         add_constant(&mut x);
@@ -104,6 +679,30 @@ mod tests {
         assert!(t_stats.get(0).unwrap().is_infinite());
     }
 
+    #[test]
+    fn test_ols_zero_residual_df() {
+        // n == k: no degree of freedom left for the residual variance
+        let y = DVector::from_row_slice(&[1.0f64, 3.0]);
+        let mut x = DMatrix::from_row_slice(2, 1, &[1.0f64, 2.0]);
+        add_constant(&mut x);
+
+        assert!(matches!(super::ols(&y, &x), Err(Error::NotEnoughSamples)));
+        assert!(matches!(
+            super::ols_cholesky(&y, &x),
+            Err(Error::NotEnoughSamples)
+        ));
+        assert!(matches!(
+            super::precompute(&x),
+            Err(Error::NotEnoughSamples)
+        ));
+
+        let y = DVector::from_row_slice(&[1.0f64, 3.0, 4.0]);
+        let mut x = DMatrix::from_row_slice(3, 1, &[1.0f64, 2.0, 3.0]);
+        add_constant(&mut x);
+        let (_betas, t_stats) = super::ols(&y, &x).unwrap();
+        assert!(t_stats.iter().all(|t| t.is_finite()));
+    }
+
     #[test]
     fn test_ols_2() {
         let sz = 400;
@@ -111,7 +710,7 @@ mod tests {
         let mu = 4.0;
         let beta = 12.;
 
-        let (mut x, y) = gen_affine_data(sz, mu, beta);
+        let (mut x, y) = gen_affine_data(sz, mu, beta).unwrap();
         add_constant(&mut x);
 
         let (beta_hat, t_stats) = super::ols(&y, &x).unwrap();
@@ -137,7 +736,7 @@ mod tests {
 
         let mut rng = ChaCha8Rng::seed_from_u64(42);
 
-        let (mut x, y) = gen_affine_data_with_whitenoise(&mut rng, sz, mu, beta);
+        let (mut x, y) = gen_affine_data_with_whitenoise(&mut rng, sz, mu, beta).unwrap();
         add_constant(&mut x);
 
         let (beta_hat, t_stats) = super::ols(&y, &x).unwrap();
@@ -200,4 +799,348 @@ mod tests {
         assert!(*t_stats.get(1).unwrap() > 1e3);
         assert!(*t_stats.get(2).unwrap() > 1e3);
     }
+
+    #[test]
+    fn test_ols_cholesky_matches_ols() {
+        let sz = 400;
+
+        let mu = 43.0;
+        let beta = 2.;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let (mut x, y) = gen_affine_data_with_whitenoise(&mut rng, sz, mu, beta).unwrap();
+        add_constant(&mut x);
+
+        let (beta_hat, t_stats) = super::ols(&y, &x).unwrap();
+        let (beta_hat_chol, t_stats_chol) = super::ols_cholesky(&y, &x).unwrap();
+
+        assert_relative_eq!(beta_hat, beta_hat_chol, epsilon = 1e-9);
+        assert_relative_eq!(t_stats, t_stats_chol, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_check_full_rank() {
+        let mut x = DMatrix::from_row_slice(
+            6,
+            2,
+            &[
+                1.0f64, 0.5, 2.0, -1.0, 3.0, 2.0, 4.0, 0.0, 5.0, 1.5, 6.0, 3.0,
+            ],
+        );
+        assert!(super::check_full_rank(&x).is_ok());
+
+        // a third column 2 * x0 - x1 and a fourth 0.1 * x1
+        let combination = x.column(0) * 2.0 - x.column(1);
+        let scaled = x.column(1) * 0.1;
+        x.extend(combination.iter().copied());
+        x.extend(scaled.iter().copied());
+        assert!(matches!(
+            super::check_full_rank(&x),
+            Err(Error::RankDeficient { rank: 2, ncols: 4 })
+        ));
+
+        // more columns than rows
+        let wide = DMatrix::from_row_slice(2, 3, &[1.0f64, 0.0, 1.0, 0.0, 1.0, 1.0]);
+        assert!(matches!(
+            super::check_full_rank(&wide),
+            Err(Error::RankDeficient { rank: 2, ncols: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_studentized_residuals() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let mut y: DVector<f64> = gen_ar_1(&mut rng, 30, 0.0, 0.5, 1.0);
+        // an outlier
+        y[12] += 8.0;
+        let mut x = DMatrix::from_fn(30, 1, |i, _| i as f64);
+        add_constant(&mut x);
+
+        let studentized = super::studentized_residuals(&y, &x).unwrap();
+
+        // t_i is the t-statistic of a dummy for observation i added to the regression
+        for i in 0..y.len() {
+            let mut with_dummy = x.clone();
+            with_dummy.extend(
+                (0..y.len())
+                    .map(|j| if i == j { 1.0 } else { 0.0 })
+                    .collect::<Vec<_>>(),
+            );
+            let (_, t_stats) = super::ols(&y, &with_dummy).unwrap();
+            assert_relative_eq!(studentized[i], t_stats[2], epsilon = 1e-8);
+        }
+
+        let largest = studentized.iamax();
+        assert_eq!(largest, 12);
+
+        assert!(matches!(
+            super::studentized_residuals(&y.rows(0, 3).into_owned(), &x.rows(0, 3).into_owned()),
+            Err(Error::NotEnoughSamples)
+        ));
+    }
+
+    #[test]
+    fn test_ols_cholesky_rank_deficient() {
+        let x = DMatrix::from_row_slice(
+            5,
+            2,
+            &[1.0f64, 2.0, 2.0, 4.0, 3.0, 6.0, 4.0, 8.0, 5.0, 10.0],
+        );
+        let y = DVector::from_row_slice(&[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+
+        let res = super::ols_cholesky(&y, &x);
+        assert!(matches!(
+            res,
+            Err(Error::RankDeficient { rank: 1, ncols: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_ols_with_provided_noise() {
+        let sz = 6;
+
+        let mu = 1.5;
+        let beta = 3.;
+
+        let noise = DVector::from_row_slice(&[0.1f64, -0.2, 0.05, 0.3, -0.1, -0.15]);
+        let (mut x, y) = gen_affine_data_with_noise(sz, mu, beta, &noise).unwrap();
+
+        let (_, y_no_noise) = gen_affine_data(sz, mu, beta).unwrap();
+        assert_relative_eq!(y, y_no_noise + &noise, epsilon = 1e-12);
+
+        add_constant(&mut x);
+        let (beta_hat, _) = super::ols(&y, &x).unwrap();
+
+        assert_relative_eq!(beta_hat[0], beta, epsilon = 0.1);
+        assert_relative_eq!(beta_hat[1], mu, epsilon = 0.5);
+    }
+
+    #[test]
+    fn test_ols_result() {
+        // y = [1, 3, 2, 5, 4] on [x, 1] with x = [1, 2, 3, 4, 5]: ssr = 3.6, n = 5, k = 2
+        let y = DVector::from_row_slice(&[1.0f64, 3.0, 2.0, 5.0, 4.0]);
+        let mut x = DMatrix::from_row_slice(5, 1, &[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+        add_constant(&mut x);
+
+        let result = super::ols_result(&y, &x, super::OlsOptions::default()).unwrap();
+        assert_eq!(
+            (result.betas.clone(), result.t_stats.clone()),
+            super::ols(&y, &x).unwrap()
+        );
+        let vcv = result.vcv;
+
+        // sigma^2 (A'A)^-1 with sigma^2 = 3.6 / 3 and A'A = [[55, 15], [15, 5]]
+        let expected = DMatrix::from_row_slice(2, 2, &[0.12, -0.36, -0.36, 1.32]);
+        assert_relative_eq!(vcv, expected, epsilon = 1e-12);
+        assert_relative_eq!(vcv, vcv.transpose(), epsilon = 1e-15);
+
+        assert_relative_eq!(result.residual_norm, 3.6.sqrt(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_wls() {
+        let y = DVector::from_row_slice(&[1.0f64, 3.0, 2.0, 5.0, 4.0, 30.0]);
+        let mut x = DMatrix::from_row_slice(6, 1, &[1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        add_constant(&mut x);
+
+        // constant weights are OLS
+        let (betas, t_stats) = super::wls(&y, &x, &DVector::from_element(6, 4.0)).unwrap();
+        let (ols_betas, ols_t_stats) = super::ols(&y, &x).unwrap();
+        assert_relative_eq!(betas, ols_betas, epsilon = 1e-9);
+        assert_relative_eq!(t_stats, ols_t_stats, epsilon = 1e-9);
+
+        // a zero weight drops the row from the fit
+        let weights = DVector::from_row_slice(&[1.0, 1.0, 1.0, 1.0, 1.0, 0.0]);
+        let (betas, _t_stats) = super::wls(&y, &x, &weights).unwrap();
+        let (expected, _t_stats) =
+            super::ols(&y.rows(0, 5).into_owned(), &x.rows(0, 5).into_owned()).unwrap();
+        assert_relative_eq!(betas, expected, epsilon = 1e-9);
+
+        assert!(matches!(
+            super::wls(&y, &x, &DVector::from_element(5, 1.0)),
+            Err(Error::InvalidParameter(_))
+        ));
+        let mut weights = DVector::from_element(6, 1.0);
+        weights[2] = f64::NAN;
+        assert!(matches!(
+            super::wls(&y, &x, &weights),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_robust_regression() {
+        let sz = 20;
+
+        let mu = 1.5;
+        let beta = 3.;
+
+        let mut noise = DVector::from_fn(sz, |i, _| if i % 2 == 0 { 0.1 } else { -0.1 });
+        noise[15] = 40.0;
+        let (mut x, y) = gen_affine_data_with_noise(sz, mu, beta, &noise).unwrap();
+        add_constant(&mut x);
+
+        let (ols_beta, _) = super::ols(&y, &x).unwrap();
+        let (robust_beta, robust_t_stats) = super::robust_regression(&y, &x, 1.345, 50).unwrap();
+
+        // the outlier pulls the OLS fit but barely the Huber one
+        assert!((ols_beta[1] - mu).abs() > 0.5);
+        assert_relative_eq!(robust_beta[0], beta, epsilon = 0.05);
+        assert_relative_eq!(robust_beta[1], mu, epsilon = 0.3);
+        assert!(robust_t_stats[0] > 10.0);
+
+        // without iterations or with a large tuning constant this is OLS
+        let (beta_0, _) = super::robust_regression(&y, &x, 1.345, 0).unwrap();
+        assert_eq!(beta_0, ols_beta);
+        let (beta_large, _) = super::robust_regression(&y, &x, 1e6, 50).unwrap();
+        assert_relative_eq!(beta_large, ols_beta, epsilon = 1e-9);
+
+        assert!(matches!(
+            super::robust_regression(&y, &x, 0.0, 50),
+            Err(Error::InvalidParameter(_))
+        ));
+
+        // a single reweighting does not settle the coefficients
+        assert!(matches!(
+            super::robust_regression(&y, &x, 1.345, 1),
+            Err(Error::NotConverged { iterations: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_coef_p_values() {
+        let t_stats = DVector::from_row_slice(&[2.228138851986f64, -2.228138851986, 0.0]);
+
+        let p_values = super::coef_p_values(&t_stats, 10).unwrap();
+        assert_relative_eq!(p_values[0], 0.05, epsilon = 1e-9);
+        assert_relative_eq!(p_values[1], 0.05, epsilon = 1e-9);
+        assert_relative_eq!(p_values[2], 1.0, epsilon = 1e-9);
+
+        assert!(super::coef_p_values(&t_stats, 0).is_err());
+    }
+
+    #[test]
+    fn test_ols_precomputed() {
+        let sz = 100;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let (mut x, y_1) = gen_affine_data_with_whitenoise(&mut rng, sz, 3.0f64, 2.0).unwrap();
+        let (_, y_2) = gen_affine_data_with_whitenoise(&mut rng, sz, -1.0f64, 0.5).unwrap();
+        add_constant(&mut x);
+
+        let precomputed = super::precompute(&x).unwrap();
+
+        for y in [y_1, y_2] {
+            let (beta_hat, t_stats) = precomputed.solve(&y);
+            let (expected_beta_hat, expected_t_stats) = super::ols(&y, &x).unwrap();
+
+            assert_relative_eq!(beta_hat, expected_beta_hat, epsilon = 1e-12);
+            assert_relative_eq!(t_stats, expected_t_stats, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_information_criteria() {
+        // statsmodels: OLS(y, add_constant(x)).fit() with
+        // y = [1, 3, 2, 5, 4], x = [1, 2, 3, 4, 5] -> ssr = 3.6, nobs = 5, k = 2
+        let ssr = 3.6f64;
+
+        let llf = -2.5 * ((2.0 * std::f64::consts::PI).ln() + (ssr / 5.0).ln() + 1.0);
+        assert_relative_eq!(llf, -6.2734325, epsilon = 1e-6);
+
+        assert_relative_eq!(super::aic(ssr, 5, 2), 16.5468650, epsilon = 1e-6);
+        assert_relative_eq!(super::bic(ssr, 5, 2), 15.7657408, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_lr_test() {
+        // y = [1, 3, 2, 5, 4] on [1] -> ssr = 10, on [1, x] with x = [1, 2, 3, 4, 5] -> ssr
+        // = 3.6: LR = 5 ln(10 / 3.6) and P(chi2(1) > LR) = erfc(sqrt(LR / 2))
+        let (statistic, p_value) = super::lr_test(10.0f64, 3.6, 5, 1);
+        assert_relative_eq!(statistic, 5.108256237660, epsilon = 1e-9);
+        assert_relative_eq!(p_value, 0.023812241842, epsilon = 1e-9);
+
+        let (statistic, p_value) = super::lr_test(3.6f64, 3.6, 5, 2);
+        assert_relative_eq!(statistic, 0.0, epsilon = 1e-12);
+        assert_relative_eq!(p_value, 1.0, epsilon = 1e-12);
+
+        // no restriction: no p-value
+        let (statistic, p_value) = super::lr_test(10.0f64, 3.6, 5, 0);
+        assert_relative_eq!(statistic, 5.108256237660, epsilon = 1e-9);
+        assert!(p_value.is_nan());
+    }
+
+    #[test]
+    fn test_wald_test() {
+        let sz = 100;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let (mut x, y) = gen_affine_data_with_whitenoise(&mut rng, sz, 3.0f64, 0.02).unwrap();
+        add_constant(&mut x);
+
+        let result = super::ols_result(&y, &x, super::OlsOptions::default()).unwrap();
+
+        // a single restriction on one coefficient is the squared t-statistic
+        let restriction = DMatrix::from_row_slice(1, 2, &[1.0, 0.0]);
+        let r = DVector::from_row_slice(&[0.0]);
+
+        let (wald, f_stat) = super::wald_test(&result, &restriction, &r).unwrap();
+        assert_relative_eq!(wald, result.t_stats[0] * result.t_stats[0], epsilon = 1e-9);
+        assert_relative_eq!(f_stat, wald, epsilon = 1e-12);
+
+        // joint restriction
+        let restriction = DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 0.0, 1.0]);
+        let r = DVector::from_row_slice(&[0.02, 3.0]);
+        let (wald, f_stat) = super::wald_test(&result, &restriction, &r).unwrap();
+        assert_relative_eq!(f_stat, wald / 2.0, epsilon = 1e-12);
+        assert!(wald < 10.0);
+
+        let r = DVector::from_row_slice(&[0.0]);
+        assert!(super::wald_test(&result, &restriction, &r).is_err());
+
+        // the fit needs at least one residual degree of freedom
+        let x = x.rows(0, 2).into_owned();
+        let y = y.rows(0, 2).into_owned();
+        assert!(matches!(
+            super::ols_result(&y, &x, super::OlsOptions::default()),
+            Err(Error::NotEnoughSamples)
+        ));
+    }
+
+    #[test]
+    fn test_ols_with_options_ddof() {
+        // y = [1, 3, 2, 5, 4] on [x, 1] with x = [1, 2, 3, 4, 5]: ssr = 3.6, n = 5, k = 2
+        let y = DVector::from_row_slice(&[1.0f64, 3.0, 2.0, 5.0, 4.0]);
+        let mut x = DMatrix::from_row_slice(5, 1, &[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+        add_constant(&mut x);
+
+        let residual_options = super::OlsOptions {
+            ddof: super::Ddof::Residual,
+        };
+        let (beta_hat, t_stats) = super::ols_with_options(&y, &x, residual_options).unwrap();
+        assert_relative_eq!(beta_hat[0], 0.8, epsilon = 1e-12);
+        assert_relative_eq!(beta_hat[1], 0.6, epsilon = 1e-12);
+        // se(slope) = sqrt(3.6 / 3 / 10)
+        assert_relative_eq!(t_stats[0], 0.8 / (0.12f64).sqrt(), epsilon = 1e-12);
+        assert_eq!(
+            (beta_hat.clone(), t_stats.clone()),
+            super::ols(&y, &x).unwrap()
+        );
+
+        let mle_options = super::OlsOptions {
+            ddof: super::Ddof::Mle,
+        };
+        let (mle_beta_hat, mle_t_stats) = super::ols_with_options(&y, &x, mle_options).unwrap();
+        assert_eq!(mle_beta_hat, beta_hat);
+        // se(slope) = sqrt(3.6 / 5 / 10)
+        assert_relative_eq!(mle_t_stats[0], 0.8 / (0.072f64).sqrt(), epsilon = 1e-12);
+        assert_relative_eq!(
+            mle_t_stats,
+            t_stats * (5.0f64 / 3.0).sqrt(),
+            epsilon = 1e-12
+        );
+    }
 }
@@ -13,12 +13,19 @@
 // limitations under the License.
 
 //! Augmented Dickey-Fuller test
-use nalgebra::{DVector, RealField, Scalar};
+use std::fmt::Debug;
+
+use nalgebra::{DMatrix, DVector, RealField, SVector, Scalar};
 use num_traits::Float;
 
-use crate::distrib::Regression;
-use crate::prelude::tools::Report;
-use crate::regression::ols;
+use crate::distrib::mackinnon::mackinnon_p_value;
+use crate::distrib::{AlphaLevel, Deterministics, PValue, Regression};
+use crate::prelude::tools::{Report, TestKind};
+use crate::regression::{
+    aic, bic, check_full_rank, coef_p_values, has_intercept, lr_test, ols, ols_result, residuals,
+    robust_regression, wls, OlsOptions,
+};
+use crate::tools::{diff, Transform, TrendSpec, UnitRootTest, LEVEL_COL};
 use crate::{tools, Error};
 
 /// Augmented Dickey-Fuller test
@@ -30,16 +37,843 @@ pub fn adf_test<F: RealField + Scalar + Float>(
     lag: usize,
     regression: Regression,
 ) -> Result<Report<F>, Error> {
+    Ok(fit_adf(y, lag, regression)?.0)
+}
+
+/// OLS fit of the (Augmented) Dickey-Fuller regression - see [`fit_adf`].
+struct AdfFit<F: Scalar> {
+    /// The target: Delta(y) aligned with the rows of the design matrix
+    delta_y: DVector<F>,
+    /// The design matrix - see `tools::prepare`
+    x: DMatrix<F>,
+    /// The regression coefficients
+    betas: DVector<F>,
+    /// The t-statistics of the coefficients
+    t_stats: DVector<F>,
+}
+
+/// Fits the (Augmented) Dickey-Fuller regression of y with lag augmenting lags by OLS:
+/// returns the report of the test and the fit.
+fn fit_adf<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<(Report<F>, AdfFit<F>), Error> {
+    let (delta_y, x, size) = tools::prepare(y, lag, regression)?;
+
+    let (betas, t_stats) = ols(&delta_y, &x)?;
+
+    let report = adf_report(t_stats[LEVEL_COL], size, lag, regression, x.ncols());
+    let fit = AdfFit {
+        delta_y,
+        x,
+        betas,
+        t_stats,
+    };
+
+    Ok((report, fit))
+}
+
+/// Returns the report of the (Augmented) Dickey-Fuller test with lag augmenting lags
+/// whose regression has size rows and ncols columns, from the t-statistic of the
+/// level.
+fn adf_report<F: Debug + Clone>(
+    test_statistic: F,
+    size: usize,
+    lag: usize,
+    regression: Regression,
+    ncols: usize,
+) -> Report<F> {
+    Report {
+        test_statistic,
+        size,
+        dropped_leading: lag + 1,
+        kind: TestKind::DickeyFuller,
+        regression: Some(regression),
+        df_resid: Some(size - ncols),
+    }
+}
+
+/// Augmented Dickey-Fuller test with any combination of deterministic terms, e.g. a
+/// quadratic trend.
+///
+/// The report carries a regression only for the combinations with tabulated critical
+/// values - see [`Deterministics`]. Same as [`adf_test`] otherwise, and see
+/// [`adf_test_with_deterministics`] for user-supplied deterministic columns.
+pub fn adf_test_with_terms<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    deterministics: Deterministics,
+) -> Result<Report<F>, Error> {
+    let (report, _betas) = adf_test_with_trend(y, lag, deterministics, TrendSpec::default())?;
+    Ok(report)
+}
+
+/// Same as [`adf_test_with_terms`] with the origin of the trend columns given by trend,
+/// also returning the regression coefficients: the level, the lag differences, then the
+/// constant, trend and quadratic trend as flagged.
+///
+/// The test statistic does not depend on the origin when a constant is included, the
+/// coefficients of the deterministic terms do - see [`TrendSpec`].
+pub fn adf_test_with_trend<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    deterministics: Deterministics,
+    trend: TrendSpec,
+) -> Result<(Report<F>, DVector<F>), Error> {
+    let (delta_y, x, size) = tools::prepare_with_trend(y, lag, deterministics, trend)?;
+
+    let (betas, t_stats) = ols(&delta_y, &x)?;
+
+    let report = Report {
+        test_statistic: t_stats[LEVEL_COL],
+        size,
+        dropped_leading: lag + 1,
+        kind: TestKind::DickeyFuller,
+        regression: deterministics.regression(),
+        df_resid: Some(size - x.ncols()),
+    };
+    Ok((report, betas))
+}
+
+/// Summary of an Augmented Dickey-Fuller test, as returned by statsmodels' adfuller -
+/// see [`adf_summary`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdfSummary<F> {
+    /// The test statistic
+    pub test_statistic: F,
+    /// The MacKinnon (1994) approximate p-value
+    pub p_value: PValue<F>,
+    /// The number of augmenting lags
+    pub used_lag: usize,
+    /// The number of observations used in the test regression
+    pub nobs: usize,
+    /// The Dickey-Fuller critical values for each alpha level, from the most to the least
+    /// stringent - see [`Report::critical_values`].
+    pub critical_values: Vec<(AlphaLevel, F)>,
+    /// The deterministic terms of the regression
+    pub regression: Regression,
+}
+
+/// Augmented Dickey-Fuller test returning the statistic together with its p-value and
+/// critical values - see [`AdfSummary`].
+///
+/// [`adf_test`] returns the lean [`Report`] this is built on.
+pub fn adf_summary<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<AdfSummary<F>, Error> {
+    let report = adf_test(y, lag, regression)?;
+
+    Ok(AdfSummary {
+        test_statistic: report.test_statistic,
+        p_value: mackinnon_p_value(report.test_statistic, regression)?,
+        used_lag: lag,
+        nobs: report.nobs_statsmodels(),
+        critical_values: report.critical_values()?,
+        regression,
+    })
+}
+
+/// Augmented Dickey-Fuller test with the test regression fitted by a Huber robust
+/// regression instead of OLS - see `regression::robust_regression` for `tuning` and
+/// `max_iter`, and the `Error::NotConverged` it returns when the cap is hit.
+///
+/// The Dickey-Fuller critical values assume an OLS fit: the statistic is exploratory,
+/// e.g. to check whether a few jumps drive the conclusion of [`adf_test`]. The report is
+/// of kind `TestKind::Other` without a regression, so its Dickey-Fuller methods refuse
+/// it.
+pub fn adf_test_robust<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+    tuning: F,
+    max_iter: usize,
+) -> Result<Report<F>, Error> {
+    let (delta_y, x, size) = tools::prepare(y, lag, regression)?;
+
+    let (_betas, t_stats) = robust_regression(&delta_y, &x, tuning, max_iter)?;
+
+    Ok(Report {
+        test_statistic: t_stats[LEVEL_COL],
+        size,
+        dropped_leading: lag + 1,
+        kind: TestKind::Other,
+        regression: None,
+        df_resid: Some(size - x.ncols()),
+    })
+}
+
+/// Augmented Dickey-Fuller test with the test regression fitted by weighted least
+/// squares with weights 1 / sigma[t]^2, e.g. with the conditional volatility of a
+/// GARCH model fitted separately - see `regression::wls`.
+///
+/// - `volatility` must have the same length as y, its first lag + 1 elements are dropped
+///   to align it with Delta(y).
+/// - Returns `Error::InvalidParameter` if a volatility is not positive and finite.
+///
+/// The Dickey-Fuller critical values assume homoskedastic errors: with weights they are
+/// an approximation, as with [`adf_test_robust`]. The report is of kind
+/// `TestKind::Other` without a regression, so its Dickey-Fuller methods refuse it.
+pub fn adf_test_weighted<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    volatility: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<Report<F>, Error> {
+    if volatility.len() != y.len() {
+        return Err(Error::InvalidParameter(format!(
+            "volatility has length {} but the series has length {}",
+            volatility.len(),
+            y.len()
+        )));
+    }
+    if let Some(i) = volatility
+        .iter()
+        .position(|v| !(Float::is_finite(*v) && *v > F::zero()))
+    {
+        return Err(Error::InvalidParameter(format!(
+            "volatility {} is not positive and finite",
+            i
+        )));
+    }
+
+    let (delta_y, x, size) = tools::prepare(y, lag, regression)?;
+
+    let weights = volatility.rows(lag + 1, size).map(|v| F::one() / (v * v));
+    let (_betas, t_stats) = wls(&delta_y, &x, &weights)?;
+
+    Ok(Report {
+        test_statistic: t_stats[LEVEL_COL],
+        size,
+        dropped_leading: lag + 1,
+        kind: TestKind::Other,
+        regression: None,
+        df_resid: Some(size - x.ncols()),
+    })
+}
+
+/// Augmented Dickey-Fuller test of a near unit root null: H0: rho >= rho0 against
+/// H1: rho < rho0, where rho is the autoregressive coefficient of y.
+///
+/// The ADF regression is centered around rho0 instead of 1: the statistic is the
+/// t-statistic of beta_1 - (rho0 - 1), with beta_1 the coefficient of y[t-1]. With
+/// rho0 = 1 this is [`adf_test`].
+///
+/// For rho0 < 1 the statistic is asymptotically standard normal under the null: the
+/// Dickey-Fuller critical values do not apply and the null is rejected for values below
+/// the normal quantile of the alpha level. The report is then of kind `TestKind::Other`
+/// without a regression, as its Dickey-Fuller methods would apply them.
+///
+/// Returns `Error::InvalidParameter` if rho0 is not in (-1, 1].
+pub fn near_unit_root_test<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    rho0: F,
+    regression: Regression,
+    lag: usize,
+) -> Result<Report<F>, Error> {
+    if rho0.is_nan() || rho0 <= -F::one() || rho0 > F::one() {
+        return Err(Error::InvalidParameter(format!(
+            "rho0 must be in (-1, 1]: {:?}",
+            rho0
+        )));
+    }
+
     let (delta_y, x, size) = tools::prepare(y, lag, regression)?;
 
+    let result = ols_result(&delta_y, &x, OlsOptions::default())?;
+    let std_err = Float::sqrt(result.vcv[(LEVEL_COL, LEVEL_COL)]);
+
+    let (kind, regression) = if rho0 == F::one() {
+        (TestKind::DickeyFuller, Some(regression))
+    } else {
+        (TestKind::Other, None)
+    };
+
+    Ok(Report {
+        test_statistic: (result.betas[LEVEL_COL] - (rho0 - F::one())) / std_err,
+        size,
+        dropped_leading: lag + 1,
+        kind,
+        regression,
+        df_resid: Some(size - x.ncols()),
+    })
+}
+
+/// Augmented Dickey-Fuller test on a statically-sized vector
+///
+/// This is an adapter: the series is copied into a `DVector` and the computation is the
+/// same as [`adf_test`]. The following steps allocate on the heap, with sizes depending
+/// on the length of the series and the lag:
+/// - `prepare`: the lagged level and differences, and the design matrix
+/// - `ols`: A'A, its inverse, A'y, the coefficients and the residuals
+pub fn adf_test_static<F: RealField + Scalar + Float, const N: usize>(
+    y: &SVector<F, N>,
+    lag: usize,
+    regression: Regression,
+) -> Result<Report<F>, Error> {
+    adf_test(&DVector::from_column_slice(y.as_slice()), lag, regression)
+}
+
+/// Augmented Dickey-Fuller test on each column of m
+/// - Each column is a series; the reports are returned in column order.
+/// - Fails on the first column for which the test fails.
+pub fn adf_test_columns<F: RealField + Scalar + Float>(
+    m: &DMatrix<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<Vec<Report<F>>, Error> {
+    m.column_iter()
+        .map(|column| adf_test(&column.into_owned(), lag, regression))
+        .collect()
+}
+
+/// Augmented Dickey-Fuller test on each window y[i..i + window] of y
+/// - The reports are returned in window order: len(y) - window + 1 of them.
+/// - If `demean_window`, each window is centered on its own mean before the test.
+/// - Returns `Error::InvalidParameter` if window is 0 or longer than y.
+///
+/// Demeaning only matters with `Regression::NoConstantNoTrend`: with a constant the
+/// mean is absorbed by the intercept and the statistic is unchanged, so the mean is not
+/// removed twice. Without a constant, a demeaned window is tested for a zero-mean unit
+/// root, but the critical values of `Regression::NoConstantNoTrend` no longer account for
+/// the estimated mean - prefer `Regression::Constant` for the tabulated distribution.
+pub fn rolling_adf<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    window: usize,
+    lag: usize,
+    regression: Regression,
+    demean_window: bool,
+) -> Result<Vec<Report<F>>, Error> {
+    if window == 0 || window > y.len() {
+        return Err(Error::InvalidParameter(format!(
+            "window must be in 1..={}: {}",
+            y.len(),
+            window
+        )));
+    }
+
+    (0..=y.len() - window)
+        .map(|i| {
+            let w = y.rows(i, window);
+            if demean_window {
+                adf_test(&w.add_scalar(-w.mean()), lag, regression)
+            } else {
+                adf_test(&w.into_owned(), lag, regression)
+            }
+        })
+        .collect()
+}
+
+/// Augmented Dickey-Fuller test also returning the residuals of the test regression,
+/// e.g. for plotting or further diagnostics.
+pub fn adf_test_with_residuals<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<(Report<F>, DVector<F>), Error> {
+    let (report, fit) = fit_adf(y, lag, regression)?;
+
+    Ok((report, residuals(&fit.delta_y, &fit.x, &fit.betas)))
+}
+
+/// Augmented Dickey-Fuller test also returning the t-statistics of all the coefficients
+/// of the test regression, e.g. to prune insignificant augmenting lags.
+///
+/// The t-statistics are in the order of the columns of the design matrix, named by
+/// [`tools::coefficient_names`]: the level, the lagged differences 1..lag, then the
+/// constant and the trend as implied by the regression. The first one is the test
+/// statistic of the report.
+pub fn adf_test_verbose<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<(Report<F>, DVector<F>), Error> {
+    let (report, fit) = fit_adf(y, lag, regression)?;
+
+    Ok((report, fit.t_stats))
+}
+
+/// Augmented Dickey-Fuller test on already prepared components
+/// - `delta_y` is Delta(y[t]) = y[t] - y[t-1]
+/// - `level` is y[t-1]
+/// - `lagged_diffs` has one column per augmenting lag: Delta(y[t-i]) for i = 1..lag
+///
+/// All three must have the same number of rows. The deterministic terms implied by
+/// `regression` are appended before solving: returns `Error::InvalidParameter` if
+/// `lagged_diffs` already has a column of ones, i.e. an explicit intercept, and
+/// `regression` implies one.
+///
+/// Other constant columns, e.g. of a single row or of an exactly linear window, are
+/// valid lagged differences and are kept: they stand in for the intercept with
+/// `Regression::NoConstantNoTrend` but are collinear with it otherwise: returns
+/// `Error::RankDeficient`.
+pub fn adf_test_from_diff<F: RealField + Scalar + Float>(
+    delta_y: &DVector<F>,
+    level: &DVector<F>,
+    lagged_diffs: &DMatrix<F>,
+    regression: Regression,
+) -> Result<Report<F>, Error> {
+    let size = delta_y.len();
+    if level.len() != size || lagged_diffs.nrows() != size {
+        return Err(Error::InvalidParameter(format!(
+            "delta_y, level and lagged_diffs must have the same number of rows: {}, {}, {}",
+            size,
+            level.len(),
+            lagged_diffs.nrows()
+        )));
+    }
+    if size == 0 {
+        return Err(Error::NotEnoughSamples);
+    }
+    if regression != Regression::NoConstantNoTrend && has_intercept(lagged_diffs) {
+        return Err(Error::InvalidParameter(
+            "lagged_diffs has a column of ones and the regression adds an intercept".into(),
+        ));
+    }
+
+    let mut x = DMatrix::zeros(size, lagged_diffs.ncols() + 1);
+    x.column_mut(LEVEL_COL).copy_from(level);
+    x.columns_mut(LEVEL_COL + 1, lagged_diffs.ncols())
+        .copy_from(lagged_diffs);
+    tools::add_deterministic_terms(&mut x, regression)?;
+
+    let (_betas, t_stats) = ols(delta_y, &x)?;
+
+    Ok(Report {
+        test_statistic: t_stats[LEVEL_COL],
+        size,
+        dropped_leading: 0,
+        kind: TestKind::DickeyFuller,
+        regression: Some(regression),
+        df_resid: Some(size - x.ncols()),
+    })
+}
+
+/// Augmented Dickey-Fuller test under all three regressions
+/// - Returns the reports for `Regression::NoConstantNoTrend`, `Regression::Constant` and
+///   `Regression::ConstantAndTrend` in that order.
+/// - The differencing and lagged columns are computed once and shared.
+pub fn adf_test_all<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+) -> Result<[Report<F>; 3], Error> {
+    let (delta_y, x, size) = tools::prepare(y, lag, Regression::NoConstantNoTrend)?;
+
+    let test = |regression: Regression| -> Result<Report<F>, Error> {
+        let mut x = x.clone();
+        tools::add_deterministic_terms(&mut x, regression)?;
+
+        let (_betas, t_stats) = ols(&delta_y, &x)?;
+
+        Ok(adf_report(
+            t_stats[LEVEL_COL],
+            size,
+            lag,
+            regression,
+            x.ncols(),
+        ))
+    };
+
+    Ok([
+        test(Regression::NoConstantNoTrend)?,
+        test(Regression::Constant)?,
+        test(Regression::ConstantAndTrend)?,
+    ])
+}
+
+/// Augmented Dickey-Fuller test with additional deterministic terms
+/// - `deterministics` are user-supplied columns (e.g. seasonal dummies or a broken trend)
+///   appended to the regression after the constant and trend implied by `regression`.
+/// - Each column must have the same length as y.
+/// - Returns `Error::RankDeficient` if a column is collinear with the other regressors.
+///
+/// With any user-supplied column, the test statistic no longer follows the tabulated
+/// Dickey-Fuller distribution: the critical values from `distrib::dickeyfuller` do not
+/// apply, and the report is of kind `TestKind::Other` without a regression.
+pub fn adf_test_with_deterministics<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+    deterministics: &[DVector<F>],
+) -> Result<Report<F>, Error> {
+    let (delta_y, x, size) =
+        tools::prepare_with_deterministics(y, lag, regression, deterministics)?;
+
+    // user-supplied columns may be collinear with the regression terms
+    check_full_rank(&x)?;
+
     let (_betas, t_stats) = ols(&delta_y, &x)?;
 
+    let (kind, regression) = if deterministics.is_empty() {
+        (TestKind::DickeyFuller, Some(regression))
+    } else {
+        (TestKind::Other, None)
+    };
+
     Ok(Report {
-        test_statistic: t_stats[0],
+        test_statistic: t_stats[LEVEL_COL],
         size,
+        dropped_leading: lag + 1,
+        kind,
+        regression,
+        df_resid: Some(size - x.ncols()),
     })
 }
 
+/// Returns the order of integration d of y: the smallest number of differences
+/// 0, 1, ..., max_d after which the Augmented Dickey-Fuller test rejects the unit root at
+/// the given alpha level.
+///
+/// - Returns `None` if the unit root is not rejected after max_d differences - see
+///   [`auto_integrate`] for the reports of the orders tested.
+/// - Each difference shortens the series by one element: returns
+///   `Error::NotEnoughSamples` if it becomes too short for the test.
+///
+/// See [`auto_integrate`] for the reports of the tests.
+pub fn integration_order<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    max_d: usize,
+    regression: Regression,
+    lag: usize,
+    alpha: AlphaLevel,
+) -> Result<Option<usize>, Error> {
+    Ok(auto_integrate(y, max_d, regression, lag, alpha)?.order)
+}
+
+/// Result of [`auto_integrate`]
+#[derive(Debug, Clone)]
+pub struct AutoIntegrateReport<F: Debug + Clone> {
+    /// The order of integration d, or `None` if the unit root is not rejected after
+    /// max_d differences
+    pub order: Option<usize>,
+    /// The series differenced d times, or max_d times if the order is not reached
+    pub differenced: DVector<F>,
+    /// The Augmented Dickey-Fuller reports on the series differenced 0, 1, ..., d times -
+    /// the last one rejects the unit root - or 0, 1, ..., max_d times if the order is not
+    /// reached.
+    pub reports: Vec<Report<F>>,
+}
+
+impl<F: Debug + Clone> AutoIntegrateReport<F> {
+    /// Returns the report on the last differenced series: the series differenced d
+    /// times, or max_d times if the order is not reached.
+    ///
+    /// # Panics
+    /// If `reports` is empty, which [`auto_integrate`] never returns.
+    pub fn report(&self) -> &Report<F> {
+        self.reports
+            .last()
+            .expect("auto_integrate tests at least the series itself")
+    }
+}
+
+/// Differences y until the Augmented Dickey-Fuller test rejects the unit root at the
+/// given alpha level, as the "find d" step of Box-Jenkins: returns the order of
+/// integration d, the series differenced d times and the report at each order.
+///
+/// If the unit root is not rejected after max_d differences, the order is `None` and
+/// the reports of all the orders tested are kept. Each difference shortens the series by
+/// one element: returns `Error::NotEnoughSamples` if it becomes too short for the test.
+pub fn auto_integrate<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    max_d: usize,
+    regression: Regression,
+    lag: usize,
+    alpha: AlphaLevel,
+) -> Result<AutoIntegrateReport<F>, Error> {
+    let mut reports = vec![];
+    let mut y_d = y.clone();
+    for d in 0..=max_d {
+        if d > 0 {
+            y_d = diff(&y_d)?;
+        }
+
+        let report = adf_test(&y_d, lag, regression)?;
+        let stationary = report.is_stationary(alpha)?;
+        reports.push(report);
+
+        if stationary {
+            return Ok(AutoIntegrateReport {
+                order: Some(d),
+                differenced: y_d,
+                reports,
+            });
+        }
+    }
+
+    Ok(AutoIntegrateReport {
+        order: None,
+        differenced: y_d,
+        reports,
+    })
+}
+
+/// Fit of the constant and the constant and trend ADF regressions of the same series -
+/// see [`compare_regressions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegressionComparison<F> {
+    /// Sum of squared residuals of the constant regression
+    pub ssr_constant: F,
+    /// Sum of squared residuals of the constant and trend regression
+    pub ssr_trend: F,
+    /// AIC of the constant regression
+    pub aic_constant: F,
+    /// AIC of the constant and trend regression
+    pub aic_trend: F,
+    /// BIC of the constant regression
+    pub bic_constant: F,
+    /// BIC of the constant and trend regression
+    pub bic_trend: F,
+    /// F statistic of the restriction that the trend coefficient is zero
+    pub f_statistic: F,
+    /// p-value of the F statistic from the F(1, n - k) distribution
+    pub f_p_value: F,
+}
+
+/// Fits the Augmented Dickey-Fuller regressions of y with a constant and with a
+/// constant and trend, on the same sample and lag, to guide the choice of the
+/// deterministic specification.
+///
+/// The F statistic tests the significance of the trend term:
+/// $F = (SSR_c - SSR_{ct}) / (SSR_{ct} / (n - k_{ct}))$.
+/// Its p-value uses the standard F distribution, which is only approximate under the
+/// unit-root null.
+pub fn compare_regressions<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+) -> Result<RegressionComparison<F>, Error> {
+    let fit = |regression: Regression| -> Result<(F, DVector<F>, usize, usize), Error> {
+        let (delta_y, x, size) = tools::prepare(y, lag, regression)?;
+        let (betas, t_stats) = ols(&delta_y, &x)?;
+        let ssr = residuals(&delta_y, &x, &betas).norm_squared();
+        Ok((ssr, t_stats, size, x.ncols()))
+    };
+
+    let (ssr_constant, _, size, k_constant) = fit(Regression::Constant)?;
+    let (ssr_trend, t_stats, _, k_trend) = fit(Regression::ConstantAndTrend)?;
+
+    let df_resid = size - k_trend;
+    let f_statistic = (ssr_constant - ssr_trend)
+        / (ssr_trend / F::from(df_resid).ok_or(Error::ConversionFailed)?);
+
+    // with a single restriction F(1, n - k) is the square of Student's t(n - k)
+    let trend_t_stat = DVector::from_element(1, t_stats[k_trend - 1]);
+    let f_p_value = coef_p_values(&trend_t_stat, df_resid)?[0];
+
+    Ok(RegressionComparison {
+        ssr_constant,
+        ssr_trend,
+        aic_constant: aic(ssr_constant, size, k_constant),
+        aic_trend: aic(ssr_trend, size, k_trend),
+        bic_constant: bic(ssr_constant, size, k_constant),
+        bic_trend: bic(ssr_trend, size, k_trend),
+        f_statistic,
+        f_p_value,
+    })
+}
+
+/// Likelihood-ratio test that y is a pure random walk - with the deterministic terms of
+/// the regression - against an ARIMA(max_lag, 1, 0): the unit root is imposed and the
+/// max_lag augmenting lags of the ADF regression are tested jointly zero.
+///
+/// Both fits drop the level term and use the same rows:
+/// - restricted: $\Delta y_t$ on the deterministic terms
+/// - unrestricted: $\Delta y_t$ on $\Delta y_{t-1}, ..., \Delta y_{t-max\_lag}$ and the
+///   deterministic terms
+///
+/// The test statistic is $n \ln(SSR_r / SSR_u)$ - see `regression::lr_test`. Under the
+/// null the regressors are stationary and it is asymptotically chi-square with max_lag
+/// degrees of freedom, e.g. p-value `1 - distrib::chi2::chi2_cdf(statistic, max_lag)`.
+///
+/// This is not a unit root test: the Dickey-Fuller critical values do not apply and
+/// the report carries no regression.
+pub fn random_walk_lr_test<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    max_lag: usize,
+    regression: Regression,
+) -> Result<Report<F>, Error> {
+    if max_lag == 0 {
+        return Err(Error::InvalidParameter(
+            "the random walk test needs at least one augmenting lag".into(),
+        ));
+    }
+
+    let (delta_y, x, size) = tools::prepare(y, max_lag, regression)?;
+
+    // impose the unit root: no level term
+    let unrestricted = x.remove_column(LEVEL_COL);
+    let restricted = unrestricted
+        .columns(max_lag, unrestricted.ncols() - max_lag)
+        .into_owned();
+
+    let ssr_unrestricted = {
+        let (betas, _t_stats) = ols(&delta_y, &unrestricted)?;
+        residuals(&delta_y, &unrestricted, &betas).norm_squared()
+    };
+    let ssr_restricted = if restricted.ncols() == 0 {
+        delta_y.norm_squared()
+    } else {
+        let (betas, _t_stats) = ols(&delta_y, &restricted)?;
+        residuals(&delta_y, &restricted, &betas).norm_squared()
+    };
+
+    let (statistic, _p_value) = lr_test(ssr_restricted, ssr_unrestricted, size, max_lag);
+
+    Ok(Report {
+        test_statistic: statistic,
+        size,
+        dropped_leading: max_lag + 1,
+        kind: TestKind::Other,
+        regression: None,
+        df_resid: Some(size - unrestricted.ncols()),
+    })
+}
+
+/// Information criterion minimized by the lag selection of [`select_lag`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfoCriterion {
+    /// Akaike information criterion - see `regression::aic`
+    Aic,
+    /// Bayesian information criterion - see `regression::bic`
+    Bic,
+}
+
+/// Which lag [`select_lag`] picks when several lags have the same information criterion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LagTieBreak {
+    /// The smallest of the tied lags, as statsmodels
+    #[default]
+    PreferSmaller,
+    /// The largest of the tied lags
+    PreferLarger,
+}
+
+/// Relative tolerance under which two information criteria are tied
+const TIE_TOLERANCE: f64 = 1e-10;
+
+/// Returns the number of augmenting lags in 0..=max_lag minimizing the information
+/// criterion of the Augmented Dickey-Fuller regression, as statsmodels' adfuller with
+/// `autolag`.
+///
+/// All the lags are fitted on the same sample: the len(y) - max_lag - 1 observations
+/// available with max_lag lags. Criteria within a relative tolerance of 1e-10 are tied
+/// and resolved by `tie_break`, so that the choice does not depend on rounding.
+pub fn select_lag<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    max_lag: usize,
+    regression: Regression,
+    criterion: InfoCriterion,
+    tie_break: LagTieBreak,
+) -> Result<usize, Error> {
+    let (delta_y, full_x, size) = tools::prepare(y, max_lag, Regression::NoConstantNoTrend)?;
+
+    let criteria = (0..=max_lag)
+        .map(|lag| {
+            let mut x = full_x.columns(LEVEL_COL, lag + 1).into_owned();
+            tools::add_deterministic_terms(&mut x, regression)?;
+
+            let (betas, _t_stats) = ols(&delta_y, &x)?;
+            let ssr = residuals(&delta_y, &x, &betas).norm_squared();
+            Ok(match criterion {
+                InfoCriterion::Aic => aic(ssr, size, x.ncols()),
+                InfoCriterion::Bic => bic(ssr, size, x.ncols()),
+            })
+        })
+        .collect::<Result<Vec<F>, Error>>()?;
+
+    best_lag(&criteria, tie_break)
+}
+
+/// Returns the index of the smallest criterion, resolving the ties within
+/// [`TIE_TOLERANCE`] with `tie_break`.
+fn best_lag<F: Float>(criteria: &[F], tie_break: LagTieBreak) -> Result<usize, Error> {
+    let tolerance = F::from(TIE_TOLERANCE).ok_or(Error::ConversionFailed)?;
+
+    let mut best: Option<(usize, F)> = None;
+    for (lag, &ic) in criteria.iter().enumerate() {
+        let better = match best {
+            Some((_, best_ic)) => {
+                let tied = Float::abs(ic - best_ic)
+                    <= tolerance * Float::max(Float::abs(ic), Float::abs(best_ic));
+                match tie_break {
+                    LagTieBreak::PreferSmaller => !tied && ic < best_ic,
+                    LagTieBreak::PreferLarger => tied || ic < best_ic,
+                }
+            }
+            None => true,
+        };
+        if better {
+            best = Some((lag, ic));
+        }
+    }
+
+    best.map(|(lag, _ic)| lag).ok_or(Error::NotEnoughSamples)
+}
+
+/// Augmented Dickey-Fuller test with the number of augmenting lags selected by
+/// [`select_lag`], then fitted on all the observations available with that lag.
+///
+/// Returns the selected lag and the report, as statsmodels' adfuller with `autolag`.
+pub fn adf_test_autolag<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    max_lag: usize,
+    regression: Regression,
+    criterion: InfoCriterion,
+    tie_break: LagTieBreak,
+) -> Result<(usize, Report<F>), Error> {
+    let lag = select_lag(y, max_lag, regression, criterion, tie_break)?;
+
+    Ok((lag, adf_test(y, lag, regression)?))
+}
+
+/// Augmented Dickey-Fuller test configuration - see [`adf_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adf {
+    /// The number of augmenting lags
+    pub lag: usize,
+    /// The deterministic terms of the regression
+    pub regression: Regression,
+}
+
+impl Adf {
+    /// Returns the minimum length of the series for the test to run: 2 * lag + 3 plus
+    /// the number of deterministic terms.
+    pub fn min_length(&self) -> usize {
+        tools::adf_min_length(self.lag, self.regression)
+    }
+}
+
+impl<F: RealField + Scalar + Float> UnitRootTest<F> for Adf {
+    fn run(&self, y: &DVector<F>) -> Result<Report<F>, Error> {
+        adf_test(y, self.lag, self.regression)
+    }
+
+    fn min_length(&self) -> usize {
+        Adf::min_length(self)
+    }
+}
+
+/// Augmented Dickey-Fuller test on a transformed series
+/// - The transform is applied to y before differencing, e.g. `Transform::LogDiff` tests
+///   the log-returns of a price series.
+/// - Returns `Error::NonPositiveValue` if a log is taken of a non-positive value.
+/// - `Report::dropped_leading` counts the observations of the original series, including
+///   the one consumed by `Transform::LogDiff`.
+pub fn adf_test_transformed<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    transform: Transform,
+    lag: usize,
+    regression: Regression,
+) -> Result<Report<F>, Error> {
+    let transformed = transform.apply(y)?;
+
+    let mut report = adf_test(&transformed, lag, regression)?;
+    report.dropped_leading += y.len() - transformed.len();
+    Ok(report)
+}
+
 /// Comparison with statsmodels.tsa.stattools.adfuller use the following code:
 /// ```python
 /// import numpy as np
@@ -71,15 +905,33 @@ pub fn adf_test<F: RealField + Scalar + Float>(
 /// adf_test(y, maxlag=2, regression='ct')
 /// ```
 ///
-/// Note: this library does not support the `autolag` yet. Tests are using the lag from
-/// statsmodels.
+/// The tests using a fixed lag use the lag selected by statsmodels' `autolag` - see
+/// [`adf_test_autolag`].
 #[cfg(test)]
 mod tests {
     use approx::assert_relative_eq;
-    use nalgebra::DVector;
+    use nalgebra::{DMatrix, DVector};
+    use rand::prelude::*;
+    use rand_chacha::ChaCha8Rng;
 
-    use crate::distrib::Regression;
+    use super::{
+        adf_summary, adf_test_all, adf_test_autolag, adf_test_columns, adf_test_from_diff,
+        adf_test_robust, adf_test_static, adf_test_transformed, adf_test_verbose,
+        adf_test_weighted, adf_test_with_deterministics, adf_test_with_residuals,
+        adf_test_with_terms, adf_test_with_trend, auto_integrate, compare_regressions,
+        integration_order, near_unit_root_test, random_walk_lr_test, rolling_adf, Adf,
+        InfoCriterion, LagTieBreak,
+    };
+    use crate::distrib::chi2::chi2_cdf;
+    use crate::distrib::mackinnon::mackinnon_critical_value;
+    use crate::distrib::{AlphaLevel, Deterministics, Regression};
     use crate::prelude::tools::{adf_test, dickeyfuller_test};
+    use crate::tools::dickeyfuller::DickeyFuller;
+    use crate::tools::ers::Ers;
+    use crate::tools::kpss::Kpss;
+    use crate::tools::{TestKind, Transform, TrendSpec, UnitRootTest, LEVEL_COL};
+    use crate::utils::{cumsum, gen_ar_1};
+    use crate::Error;
 
     const Y: [f64; 11] = [
         -1.06714348,
@@ -119,6 +971,10 @@ mod tests {
 
         let report = adf_test(&y, lag, Regression::Constant).unwrap();
         assert_eq!(report.size, 8);
+        assert_eq!(report.dropped_leading, 3);
+        assert_eq!(report.dropped_leading + report.size, y.len());
+        assert_eq!(report.regression, Some(Regression::Constant));
+        assert_eq!(report.df_resid, Some(4));
         assert_relative_eq!(report.test_statistic, 0.486121422662f64, epsilon = 1e-9);
         // Results of Dickey-Fuller Test:
         // Test Statistic                0.486121422662
@@ -146,6 +1002,17 @@ mod tests {
         // Critical Value (1%)                  -5.282515
         // Critical Value (5%)                  -3.985264
         // Critical Value (10%)                  -3.44724
+
+        // report.size is the sample size statsmodels uses for its critical values
+        for (alpha, expected) in [
+            (AlphaLevel::OnePercent, -5.282515),
+            (AlphaLevel::FivePercent, -3.985264),
+            (AlphaLevel::TenPercent, -3.44724),
+        ] {
+            let critical_value: f64 =
+                mackinnon_critical_value(Regression::ConstantAndTrend, report.size, alpha).unwrap();
+            assert_relative_eq!(critical_value, expected, epsilon = 1e-6);
+        }
     }
 
     #[test]
@@ -164,4 +1031,750 @@ mod tests {
         assert_eq!(report.test_statistic, df_report.test_statistic);
         assert_eq!(report.size, df_report.size);
     }
+
+    #[test]
+    fn test_adf_transformed() {
+        let lag = 1;
+        let prices = DVector::from_row_slice(&Y[..]).map(|v| v.exp());
+
+        let report =
+            adf_test_transformed(&prices, Transform::Log, lag, Regression::NoConstantNoTrend)
+                .unwrap();
+        let expected = adf_test(
+            &DVector::from_row_slice(&Y[..]),
+            lag,
+            Regression::NoConstantNoTrend,
+        )
+        .unwrap();
+
+        assert_relative_eq!(
+            report.test_statistic,
+            expected.test_statistic,
+            epsilon = 1e-9
+        );
+        assert_eq!(report.size, expected.size);
+
+        let report =
+            adf_test_transformed(&prices, Transform::LogDiff, lag, Regression::Constant).unwrap();
+        assert_eq!(report.size, expected.size - 1);
+        assert_eq!(report.dropped_leading, lag + 2);
+        assert_eq!(report.dropped_leading + report.size, prices.len());
+    }
+
+    #[test]
+    fn test_adf_transformed_non_positive() {
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let res = adf_test_transformed(&y, Transform::LogDiff, 1, Regression::Constant);
+        assert!(matches!(res, Err(Error::NonPositiveValue(0))));
+    }
+
+    #[test]
+    fn test_adf_with_deterministics() {
+        let lag = 1;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        // a constant supplied by the caller is the same as Regression::Constant
+        let constant = DVector::from_element(y.len(), 1.0);
+        let report =
+            adf_test_with_deterministics(&y, lag, Regression::NoConstantNoTrend, &[constant])
+                .unwrap();
+        let expected = adf_test(&y, lag, Regression::Constant).unwrap();
+
+        assert_eq!(report.size, expected.size);
+        assert_relative_eq!(
+            report.test_statistic,
+            expected.test_statistic,
+            epsilon = 1e-9
+        );
+
+        let report = adf_test_with_deterministics(&y, lag, Regression::Constant, &[]).unwrap();
+        assert_eq!(report.test_statistic, expected.test_statistic);
+    }
+
+    #[test]
+    fn test_adf_with_trend() {
+        let lag = 1;
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y: DVector<f64> = gen_ar_1(&mut rng, 60, 0.5, 0.7, 1.0);
+
+        for deterministics in [
+            Deterministics::CONST | Deterministics::TREND,
+            Deterministics::CONST | Deterministics::TREND | Deterministics::TREND2,
+        ] {
+            let (expected, expected_betas) =
+                adf_test_with_trend(&y, lag, deterministics, TrendSpec::OneBased).unwrap();
+
+            for trend in [TrendSpec::ZeroBased, TrendSpec::Centered] {
+                let (report, betas) = adf_test_with_trend(&y, lag, deterministics, trend).unwrap();
+
+                // the level t-statistic and coefficient are invariant to the origin
+                assert_relative_eq!(
+                    report.test_statistic,
+                    expected.test_statistic,
+                    epsilon = 1e-9
+                );
+                assert_relative_eq!(betas[LEVEL_COL], expected_betas[LEVEL_COL], epsilon = 1e-9);
+
+                // the constant is not
+                let constant = lag + 1;
+                assert!((betas[constant] - expected_betas[constant]).abs() > 1e-6);
+            }
+        }
+
+        // the default origin is the one of adf_test
+        let (report, _betas) = adf_test_with_trend(
+            &y,
+            lag,
+            Regression::ConstantAndTrend.into(),
+            TrendSpec::default(),
+        )
+        .unwrap();
+        let expected = adf_test(&y, lag, Regression::ConstantAndTrend).unwrap();
+        assert_eq!(report.test_statistic, expected.test_statistic);
+    }
+
+    #[test]
+    fn test_adf_with_terms() {
+        let lag = 2;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        for regression in [
+            Regression::NoConstantNoTrend,
+            Regression::Constant,
+            Regression::ConstantAndTrend,
+        ] {
+            let report = adf_test_with_terms(&y, lag, regression.into()).unwrap();
+            let expected = adf_test(&y, lag, regression).unwrap();
+            assert_eq!(report.test_statistic, expected.test_statistic);
+            assert_eq!(report.regression, Some(regression));
+        }
+
+        // the quadratic trend t^2 over the rows of the regression, as a user column
+        let t2 = DVector::from_fn(y.len(), |i, _| (i as f64 - lag as f64).powi(2));
+        let expected =
+            adf_test_with_deterministics(&y, lag, Regression::ConstantAndTrend, &[t2]).unwrap();
+
+        let deterministics = Deterministics::CONST | Deterministics::TREND | Deterministics::TREND2;
+        let report = adf_test_with_terms(&y, lag, deterministics).unwrap();
+        assert_relative_eq!(
+            report.test_statistic,
+            expected.test_statistic,
+            epsilon = 1e-9
+        );
+        assert_eq!(report.df_resid, expected.df_resid);
+        assert_eq!(report.regression, None);
+        assert!(report.critical_values().is_err());
+    }
+
+    #[test]
+    fn test_adf_with_deterministics_critical_values() {
+        let lag = 0;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        // without user columns, this is adf_test
+        let report = adf_test_with_deterministics(&y, lag, Regression::Constant, &[]).unwrap();
+        let expected = adf_test(&y, lag, Regression::Constant).unwrap();
+        assert_eq!(report.test_statistic, expected.test_statistic);
+        assert_eq!(report.kind, TestKind::DickeyFuller);
+        assert_eq!(
+            report.critical_values().unwrap(),
+            expected.critical_values().unwrap()
+        );
+
+        // with a user column, the Dickey-Fuller tables do not apply
+        let dummy = DVector::from_fn(y.len(), |i, _| if i >= 8 { 1.0 } else { 0.0 });
+        let report = adf_test_with_deterministics(&y, lag, Regression::Constant, &[dummy]).unwrap();
+        assert_eq!(report.kind, TestKind::Other);
+        assert_eq!(report.regression, None);
+        assert!(report.critical_values().is_err());
+        assert!(report.is_stationary(AlphaLevel::FivePercent).is_err());
+    }
+
+    #[test]
+    fn test_adf_with_collinear_deterministics() {
+        let lag = 1;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        // a second constant collapses the design: [y_{t-1}, Δy_{t-1}, 1, 2]
+        let constant = DVector::from_element(y.len(), 2.0);
+        let res = adf_test_with_deterministics(&y, lag, Regression::Constant, &[constant]);
+        assert!(matches!(
+            res,
+            Err(Error::RankDeficient { rank: 3, ncols: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_adf_all() {
+        let lag = 1;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let reports = adf_test_all(&y, lag).unwrap();
+
+        for (report, regression) in reports.iter().zip([
+            Regression::NoConstantNoTrend,
+            Regression::Constant,
+            Regression::ConstantAndTrend,
+        ]) {
+            let expected = adf_test(&y, lag, regression).unwrap();
+            assert_eq!(report.regression, Some(regression));
+            assert_eq!(report.size, expected.size);
+            assert_relative_eq!(
+                report.test_statistic,
+                expected.test_statistic,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_adf_from_diff() {
+        let lag = 2;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let (delta_y, x, _) =
+            crate::tools::prepare(&y, lag, Regression::NoConstantNoTrend).unwrap();
+        let level = x.column(0).into_owned();
+        let lagged_diffs = x.columns(1, lag).into_owned();
+
+        let report =
+            adf_test_from_diff(&delta_y, &level, &lagged_diffs, Regression::Constant).unwrap();
+        let expected = adf_test(&y, lag, Regression::Constant).unwrap();
+
+        assert_eq!(report.size, expected.size);
+        assert_relative_eq!(
+            report.test_statistic,
+            expected.test_statistic,
+            epsilon = 1e-12
+        );
+
+        let res = adf_test_from_diff(
+            &delta_y,
+            &level.clone().remove_row(0),
+            &lagged_diffs,
+            Regression::Constant,
+        );
+        assert!(res.is_err());
+
+        // the regression already adds the intercept
+        let mut with_constant = lagged_diffs.clone();
+        with_constant.extend(vec![1.0; delta_y.len()]);
+        let res = adf_test_from_diff(&delta_y, &level, &with_constant, Regression::Constant);
+        assert!(matches!(res, Err(Error::InvalidParameter(_))));
+        assert!(adf_test_from_diff(
+            &delta_y,
+            &level,
+            &with_constant,
+            Regression::NoConstantNoTrend
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_adf_from_diff_constant_lagged_diff() {
+        let lag = 1;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let (delta_y, x, _) =
+            crate::tools::prepare(&y, lag, Regression::NoConstantNoTrend).unwrap();
+        let level = x.column(0).into_owned();
+        // e.g. the lagged differences of an exactly linear window
+        let constant = DMatrix::from_element(delta_y.len(), 1, 2.0);
+
+        // the constant column stands in for the intercept
+        let report =
+            adf_test_from_diff(&delta_y, &level, &constant, Regression::NoConstantNoTrend).unwrap();
+        let expected = adf_test_from_diff(
+            &delta_y,
+            &level,
+            &DMatrix::zeros(delta_y.len(), 0),
+            Regression::Constant,
+        )
+        .unwrap();
+        assert_relative_eq!(
+            report.test_statistic,
+            expected.test_statistic,
+            epsilon = 1e-9
+        );
+
+        // ... and is collinear with the one the regression adds
+        let res = adf_test_from_diff(&delta_y, &level, &constant, Regression::Constant);
+        assert!(matches!(
+            res,
+            Err(Error::RankDeficient { rank: 2, ncols: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_adf_with_residuals() {
+        let lag = 2;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let (report, residuals) = adf_test_with_residuals(&y, lag, Regression::Constant).unwrap();
+        let expected = adf_test(&y, lag, Regression::Constant).unwrap();
+
+        assert_eq!(report.test_statistic, expected.test_statistic);
+        assert_eq!(residuals.len(), report.size);
+        // with a constant the residuals sum to zero
+        assert_relative_eq!(residuals.sum(), 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_adf_columns() {
+        let lag = 1;
+        let y = DVector::from_row_slice(&Y[..]);
+        let y_rev = DVector::from_iterator(Y.len(), Y.iter().rev().cloned());
+
+        let m = DMatrix::from_columns(&[y.clone(), y_rev.clone()]);
+
+        let reports = adf_test_columns(&m, lag, Regression::Constant).unwrap();
+        assert_eq!(reports.len(), 2);
+        for (report, y) in reports.iter().zip([y, y_rev]) {
+            let expected = adf_test(&y, lag, Regression::Constant).unwrap();
+            assert_eq!(report.test_statistic, expected.test_statistic);
+            assert_eq!(report.size, expected.size);
+        }
+    }
+
+    #[test]
+    fn test_adf_static() {
+        let lag = 2;
+        let y = nalgebra::SVector::<f64, 11>::from_row_slice(&Y[..]);
+
+        let report = adf_test_static(&y, lag, Regression::Constant).unwrap();
+        let expected =
+            adf_test(&DVector::from_row_slice(&Y[..]), lag, Regression::Constant).unwrap();
+
+        assert_eq!(report.test_statistic, expected.test_statistic);
+        assert_eq!(report.size, expected.size);
+    }
+
+    #[test]
+    fn test_unit_root_test_suite() {
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let suite: Vec<Box<dyn UnitRootTest<f64>>> = vec![
+            Box::new(Adf {
+                lag: 1,
+                regression: Regression::Constant,
+            }),
+            Box::new(DickeyFuller {
+                regression: Regression::Constant,
+            }),
+        ];
+
+        let reports = suite
+            .iter()
+            .map(|test| test.run(&y))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            reports[0].test_statistic,
+            adf_test(&y, 1, Regression::Constant)
+                .unwrap()
+                .test_statistic
+        );
+        assert_eq!(
+            reports[1].test_statistic,
+            dickeyfuller_test(&y, Regression::Constant)
+                .unwrap()
+                .test_statistic
+        );
+    }
+
+    #[test]
+    fn test_min_length() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1(&mut rng, 100, 0.0f64, 0.5, 1.0);
+
+        let mut suite: Vec<Box<dyn UnitRootTest<f64>>> = vec![];
+        for regression in [Regression::Constant, Regression::ConstantAndTrend] {
+            suite.push(Box::new(DickeyFuller { regression }));
+            suite.push(Box::new(Ers { regression, lag: 2 }));
+            suite.push(Box::new(Kpss::new(regression)));
+            for lag in [0, 1, 4] {
+                suite.push(Box::new(Adf { lag, regression }));
+            }
+        }
+        suite.push(Box::new(Adf {
+            lag: 3,
+            regression: Regression::NoConstantNoTrend,
+        }));
+
+        for test in suite.iter() {
+            let n = test.min_length();
+            assert!(test.run(&y.rows(0, n).into_owned()).is_ok());
+            assert!(matches!(
+                test.run(&y.rows(0, n - 1).into_owned()),
+                Err(Error::NotEnoughSamples)
+            ));
+        }
+
+        let adf = Adf {
+            lag: 2,
+            regression: Regression::Constant,
+        };
+        assert_eq!(adf.min_length(), 8);
+    }
+
+    #[test]
+    fn test_integration_order() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let noise: DVector<f64> = gen_ar_1(&mut rng, 200, 0.0, 0.0, 1.0);
+        let walk = DVector::from_iterator(
+            noise.len(),
+            noise.iter().scan(0.0, |acc, e| {
+                *acc += e;
+                Some(*acc)
+            }),
+        );
+        let integrated_walk = DVector::from_iterator(
+            walk.len(),
+            walk.iter().scan(0.0, |acc, e| {
+                *acc += e;
+                Some(*acc)
+            }),
+        );
+
+        for (y, expected) in [(noise, 0), (walk, 1), (integrated_walk, 2)] {
+            let d =
+                integration_order(&y, 3, Regression::Constant, 1, AlphaLevel::FivePercent).unwrap();
+            assert_eq!(d, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_integration_order_not_reached() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let walk: DVector<f64> = gen_ar_1(&mut rng, 200, 0.0, 1.0, 1.0);
+
+        let res = integration_order(&walk, 0, Regression::Constant, 1, AlphaLevel::FivePercent);
+        assert!(matches!(res, Ok(None)));
+
+        let short = DVector::from_row_slice(&Y[..4]);
+        let res = integration_order(&short, 2, Regression::Constant, 1, AlphaLevel::FivePercent);
+        assert!(matches!(res, Err(Error::NotEnoughSamples)));
+    }
+
+    #[test]
+    fn test_auto_integrate() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let walk: DVector<f64> = gen_ar_1(&mut rng, 200, 0.0, 1.0, 1.0);
+
+        let result =
+            auto_integrate(&walk, 3, Regression::Constant, 1, AlphaLevel::FivePercent).unwrap();
+        assert_eq!(result.order, Some(1));
+        assert_eq!(result.reports.len(), 2);
+        assert_eq!(result.differenced, crate::tools::diff(&walk).unwrap());
+
+        let expected = adf_test(&result.differenced, 1, Regression::Constant).unwrap();
+        assert_eq!(result.report().test_statistic, expected.test_statistic);
+        assert_eq!(result.report().size, walk.len() - 3);
+
+        let levels = adf_test(&walk, 1, Regression::Constant).unwrap();
+        assert_eq!(result.reports[0].test_statistic, levels.test_statistic);
+        assert!(!result.reports[0]
+            .is_stationary(AlphaLevel::FivePercent)
+            .unwrap());
+
+        // not reached: the reports already computed are kept
+        let result =
+            auto_integrate(&walk, 0, Regression::Constant, 1, AlphaLevel::FivePercent).unwrap();
+        assert_eq!(result.order, None);
+        assert_eq!(result.reports.len(), 1);
+        assert_eq!(result.differenced, walk);
+        assert_eq!(result.report().test_statistic, levels.test_statistic);
+    }
+
+    #[test]
+    fn test_compare_regressions() {
+        let lag = 1;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let comparison = compare_regressions(&y, lag).unwrap();
+        assert!(comparison.ssr_trend <= comparison.ssr_constant);
+
+        // the F statistic of a single restriction is the squared t-statistic of the trend
+        let (delta_y, x, size) =
+            crate::tools::prepare(&y, lag, Regression::ConstantAndTrend).unwrap();
+        let (_betas, t_stats) = crate::regression::ols(&delta_y, &x).unwrap();
+        let t_trend = t_stats[x.ncols() - 1];
+        assert_relative_eq!(comparison.f_statistic, t_trend * t_trend, epsilon = 1e-9);
+        assert!(comparison.f_p_value > 0.0 && comparison.f_p_value < 1.0);
+
+        // the criteria differ by the extra parameter penalty and the fit
+        assert_relative_eq!(
+            comparison.aic_trend - comparison.aic_constant,
+            size as f64 * (comparison.ssr_trend / comparison.ssr_constant).ln() + 2.0,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            comparison.bic_trend - comparison.bic_constant,
+            size as f64 * (comparison.ssr_trend / comparison.ssr_constant).ln()
+                + (size as f64).ln(),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_adf_robust() {
+        let lag = 1;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        // with a large tuning constant no observation is downweighted
+        let report = adf_test_robust(&y, lag, Regression::Constant, 1e6, 20).unwrap();
+        let expected = adf_test(&y, lag, Regression::Constant).unwrap();
+        assert_eq!(report.size, expected.size);
+        assert_eq!(report.df_resid, expected.df_resid);
+        assert_relative_eq!(
+            report.test_statistic,
+            expected.test_statistic,
+            epsilon = 1e-9
+        );
+
+        let report = adf_test_robust(&y, lag, Regression::Constant, 1.345, 20).unwrap();
+        assert!(report.test_statistic.is_finite());
+
+        // the statistic is not compared with the Dickey-Fuller tables
+        assert_eq!(report.kind, TestKind::Other);
+        assert_eq!(report.regression, None);
+        assert!(report.is_stationary(AlphaLevel::FivePercent).is_err());
+    }
+
+    #[test]
+    fn test_adf_weighted() {
+        let lag = 1;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        // a constant volatility does not change the statistic
+        let volatility = DVector::from_element(y.len(), 2.0);
+        let report = adf_test_weighted(&y, &volatility, lag, Regression::Constant).unwrap();
+        let expected = adf_test(&y, lag, Regression::Constant).unwrap();
+        assert_eq!(report.size, expected.size);
+        assert_eq!(report.df_resid, expected.df_resid);
+        assert_relative_eq!(
+            report.test_statistic,
+            expected.test_statistic,
+            epsilon = 1e-9
+        );
+
+        // a volatility cluster downweights its rows
+        let volatility = DVector::from_fn(y.len(), |i, _| if i >= 8 { 5.0 } else { 1.0 });
+        let report = adf_test_weighted(&y, &volatility, lag, Regression::Constant).unwrap();
+        assert!((report.test_statistic - expected.test_statistic).abs() > 1e-3);
+
+        // the statistic is not compared with the Dickey-Fuller tables
+        assert_eq!(report.kind, TestKind::Other);
+        assert!(report.critical_values().is_err());
+        assert!(report.is_stationary(AlphaLevel::FivePercent).is_err());
+        assert!(report.decisions().is_err());
+        assert!(report.margin(AlphaLevel::FivePercent).is_err());
+
+        assert!(matches!(
+            adf_test_weighted(
+                &y,
+                &volatility.rows(0, 10).into_owned(),
+                lag,
+                Regression::Constant
+            ),
+            Err(Error::InvalidParameter(_))
+        ));
+        let mut volatility = DVector::from_element(y.len(), 1.0);
+        volatility[4] = 0.0;
+        assert!(matches!(
+            adf_test_weighted(&y, &volatility, lag, Regression::Constant),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_adf_summary() {
+        let lag = 0;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let summary = adf_summary(&y, lag, Regression::ConstantAndTrend).unwrap();
+        let report = adf_test(&y, lag, Regression::ConstantAndTrend).unwrap();
+
+        assert_eq!(summary.test_statistic, report.test_statistic);
+        assert_eq!(summary.used_lag, lag);
+        assert_eq!(summary.nobs, 10);
+        assert_eq!(summary.regression, Regression::ConstantAndTrend);
+        assert_eq!(summary.critical_values, report.critical_values().unwrap());
+        // statsmodels: p-value 0.00442477220907
+        assert!(!summary.p_value.is_clamped());
+        assert_relative_eq!(summary.p_value.value(), 0.00442477220907, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_near_unit_root_test() {
+        let y = DVector::from_row_slice(&Y[..]);
+
+        // rho0 = 1 is the Augmented Dickey-Fuller test
+        let expected = adf_test(&y, 1, Regression::Constant).unwrap();
+        let report = near_unit_root_test(&y, 1.0, Regression::Constant, 1).unwrap();
+        assert_relative_eq!(
+            report.test_statistic,
+            expected.test_statistic,
+            epsilon = 1e-12
+        );
+        assert_eq!(report.size, expected.size);
+        assert_eq!(report.df_resid, expected.df_resid);
+        assert_eq!(report.kind, TestKind::DickeyFuller);
+        assert_eq!(report.regression, Some(Regression::Constant));
+
+        // equivalent to the t-statistic of y[t-1] in the regression of
+        // Delta(y[t]) - (rho0 - 1) * y[t-1]
+        let rho0 = 0.8;
+        let (delta_y, x, _size) = crate::tools::prepare(&y, 1, Regression::Constant).unwrap();
+        let target = delta_y - x.column(0) * (rho0 - 1.0);
+        let (_betas, t_stats) = crate::regression::ols(&target, &x).unwrap();
+
+        let report = near_unit_root_test(&y, rho0, Regression::Constant, 1).unwrap();
+        assert_relative_eq!(report.test_statistic, t_stats[0], epsilon = 1e-9);
+        assert!(report.test_statistic > expected.test_statistic);
+
+        // the statistic is not compared with the Dickey-Fuller tables
+        assert_eq!(report.kind, TestKind::Other);
+        assert_eq!(report.regression, None);
+        assert!(report.is_stationary(AlphaLevel::FivePercent).is_err());
+
+        // a persistent but stationary AR(1): the unit root is rejected but not rho >= 0.9
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1(&mut rng, 1000, 0.0, 0.9, 1.0);
+        let report = adf_test(&y, 1, Regression::Constant).unwrap();
+        assert!(report.test_statistic < -3.5);
+        let report = near_unit_root_test(&y, 0.9, Regression::Constant, 1).unwrap();
+        assert!(report.test_statistic > -1.645);
+
+        assert!(matches!(
+            near_unit_root_test(&y, 1.5, Regression::Constant, 1),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            near_unit_root_test(&y, -1.0, Regression::Constant, 1),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_adf_test_verbose() {
+        let lag = 2;
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let (report, t_stats) = adf_test_verbose(&y, lag, Regression::ConstantAndTrend).unwrap();
+        let expected = adf_test(&y, lag, Regression::ConstantAndTrend).unwrap();
+        assert_eq!(report.test_statistic, expected.test_statistic);
+        assert_eq!(report.size, expected.size);
+
+        let names = crate::tools::coefficient_names(lag, Regression::ConstantAndTrend);
+        assert_eq!(t_stats.len(), names.len());
+        assert_eq!(t_stats[0], report.test_statistic);
+    }
+
+    #[test]
+    fn test_adf_test_autolag() {
+        let y = DVector::from_row_slice(&Y[..]);
+
+        // statsmodels' adfuller(y, maxlag=2, autolag="AIC") - see the fixed-lag tests.
+        // The lags are selected on the 8 rows of maxlag = 2, the reported number of
+        // observations is the one of the refit at the selected lag.
+        for (regression, expected_lag, expected, expected_nobs) in [
+            (Regression::NoConstantNoTrend, 1, -0.417100483298f64, 9),
+            (Regression::Constant, 2, 0.486121422662, 8),
+            (Regression::ConstantAndTrend, 0, -4.20337098854, 10),
+        ] {
+            let (lag, report) = adf_test_autolag(
+                &y,
+                2,
+                regression,
+                InfoCriterion::Aic,
+                LagTieBreak::default(),
+            )
+            .unwrap();
+            assert_eq!(lag, expected_lag);
+            assert_relative_eq!(report.test_statistic, expected, epsilon = 1e-9);
+            assert_eq!(report.nobs_statsmodels(), expected_nobs);
+        }
+    }
+
+    #[test]
+    fn test_random_walk_lr_test() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let n = 500;
+        let max_lag = 2;
+
+        // a pure random walk
+        let y: DVector<f64> = gen_ar_1(&mut rng, n, 0.0, 1.0, 1.0);
+        let report = random_walk_lr_test(&y, max_lag, Regression::Constant).unwrap();
+        assert_eq!(report.size, n - max_lag - 1);
+        assert_eq!(report.df_resid, Some(report.size - 3));
+        assert_eq!(report.regression, None);
+        assert!(1.0 - chi2_cdf(report.test_statistic, max_lag) > 0.05);
+
+        // an ARIMA(1, 1, 0)
+        let delta_y: DVector<f64> = gen_ar_1(&mut rng, n, 0.0, 0.6, 1.0);
+        let y = cumsum(&delta_y);
+        for regression in [Regression::NoConstantNoTrend, Regression::Constant] {
+            let report = random_walk_lr_test(&y, max_lag, regression).unwrap();
+            assert!(1.0 - chi2_cdf(report.test_statistic, max_lag) < 0.01);
+        }
+
+        assert!(matches!(
+            random_walk_lr_test(&y, 0, Regression::Constant),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_select_lag_tie_break() {
+        use super::best_lag;
+
+        let criteria = [3.0f64, 1.0, 2.0, 1.0, 1.0 + 1e-14, 5.0];
+        assert_eq!(best_lag(&criteria, LagTieBreak::PreferSmaller).unwrap(), 1);
+        assert_eq!(best_lag(&criteria, LagTieBreak::PreferLarger).unwrap(), 4);
+
+        // a strictly smaller criterion wins regardless of the tie break
+        let criteria = [1.0f64, 1.0, 0.5, 1.0];
+        assert_eq!(best_lag(&criteria, LagTieBreak::PreferSmaller).unwrap(), 2);
+        assert_eq!(best_lag(&criteria, LagTieBreak::PreferLarger).unwrap(), 2);
+
+        assert!(best_lag::<f64>(&[], LagTieBreak::PreferSmaller).is_err());
+    }
+
+    #[test]
+    fn test_rolling_adf() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1(&mut rng, 60, 2.0f64, 0.5, 1.0);
+        let window = 40;
+
+        let reports = rolling_adf(&y, window, 1, Regression::Constant, false).unwrap();
+        assert_eq!(reports.len(), 21);
+        let expected = adf_test(&y.rows(5, window).into_owned(), 1, Regression::Constant).unwrap();
+        assert_eq!(reports[5].test_statistic, expected.test_statistic);
+
+        // with a constant the window mean is absorbed by the intercept
+        let demeaned = rolling_adf(&y, window, 1, Regression::Constant, true).unwrap();
+        for (a, b) in reports.iter().zip(demeaned.iter()) {
+            assert_relative_eq!(a.test_statistic, b.test_statistic, epsilon = 1e-9);
+        }
+
+        // without a constant the mean of 4 hides the mean reversion unless removed
+        let raw = rolling_adf(&y, window, 1, Regression::NoConstantNoTrend, false).unwrap();
+        let demeaned = rolling_adf(&y, window, 1, Regression::NoConstantNoTrend, true).unwrap();
+        for (a, b) in raw.iter().zip(demeaned.iter()) {
+            assert!(b.test_statistic < a.test_statistic);
+        }
+
+        assert!(matches!(
+            rolling_adf(&y, 0, 1, Regression::Constant, false),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            rolling_adf(&y, 61, 1, Regression::Constant, false),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
 }
@@ -0,0 +1,174 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cointegration of pairs of series
+use nalgebra::{DMatrix, DVector, RealField, Scalar};
+use num_traits::Float;
+
+use crate::distrib::mackinnon::engle_granger_p_value;
+use crate::distrib::Regression;
+use crate::prelude::tools::Report;
+use crate::regression::{ols, residuals};
+use crate::tools::add_deterministic_terms;
+use crate::tools::adf::adf_test;
+use crate::Error;
+
+/// Two-step test of the spread between y and x, e.g. for pairs trading on log-prices:
+/// 1. the hedge ratio is the slope of the OLS regression of y on x and a constant
+/// 2. the spread y - hedge_ratio * x - intercept is tested with [`adf_test`] without
+///    deterministic terms, as the residuals have zero mean.
+///
+/// Returns the hedge ratio and the ADF report on the spread.
+///
+/// As the hedge ratio is estimated, the test statistic follows the Engle-Granger
+/// distribution rather than the Dickey-Fuller one: the critical values from
+/// `distrib::dickeyfuller` are too lenient and over-reject the null of no cointegration.
+pub fn spread_adf<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    x: &DVector<F>,
+    lag: usize,
+) -> Result<(F, Report<F>), Error> {
+    if y.len() != x.len() {
+        return Err(Error::InvalidParameter(format!(
+            "y and x must have the same length, got {} and {}",
+            y.len(),
+            x.len()
+        )));
+    }
+
+    let mut design = DMatrix::from_column_slice(x.len(), 1, x.as_slice());
+    add_deterministic_terms(&mut design, Regression::Constant)?;
+
+    let (betas, _t_stats) = ols(y, &design)?;
+    let spread = residuals(y, &design, &betas);
+
+    let report = adf_test(&spread, lag, Regression::NoConstantNoTrend)?;
+
+    Ok((betas[0], report))
+}
+
+/// Returns the N x N matrix of the Engle-Granger p-values of every ordered pair of a
+/// basket of series, e.g. to screen for cointegrated pairs.
+///
+/// The entry (i, j) is the MacKinnon p-value of the [`spread_adf`] statistic of
+/// series[i] on series[j] - see `distrib::mackinnon::engle_granger_p_value`. The test is
+/// not symmetric: (i, j) and (j, i) regress on different series. The diagonal is NaN.
+///
+/// A p-value clamped to the fitted region of the approximation is reported at its bound.
+///
+/// With the `rayon` feature, the pairs are tested in parallel.
+pub fn cointegration_matrix<F: RealField + Scalar + Float>(
+    series: &[DVector<F>],
+    lag: usize,
+) -> Result<DMatrix<F>, Error> {
+    let n = series.len();
+
+    let p_value = |k: usize| -> Result<F, Error> {
+        let (i, j) = (k / n, k % n);
+        if i == j {
+            return Ok(F::nan());
+        }
+
+        let (_hedge_ratio, report) = spread_adf(&series[i], &series[j], lag)?;
+        Ok(engle_granger_p_value(report.test_statistic)?.value())
+    };
+
+    #[cfg(feature = "rayon")]
+    let p_values = {
+        use rayon::prelude::*;
+        (0..n * n)
+            .into_par_iter()
+            .map(p_value)
+            .collect::<Result<Vec<F>, Error>>()?
+    };
+    #[cfg(not(feature = "rayon"))]
+    let p_values = (0..n * n).map(p_value).collect::<Result<Vec<F>, Error>>()?;
+
+    Ok(DMatrix::from_row_slice(n, n, &p_values))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::distrib::AlphaLevel;
+    use crate::utils::{gen_ar_1, gen_cointegrated_pair, gen_independent_walks};
+
+    #[test]
+    fn test_spread_adf_cointegrated() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let n = 200;
+        let x: DVector<f64> = gen_ar_1(&mut rng, n, 0.0, 1.0, 1.0);
+        let noise: DVector<f64> = gen_ar_1(&mut rng, n, 0.0, 0.3, 0.5);
+        let y = x.map(|v| 2.0 * v + 1.0) + noise;
+
+        let (hedge_ratio, report) = spread_adf(&y, &x, 1).unwrap();
+
+        assert_relative_eq!(hedge_ratio, 2.0, epsilon = 0.05);
+        assert!(report.is_stationary(AlphaLevel::OnePercent).unwrap());
+    }
+
+    #[test]
+    fn test_spread_adf_length_mismatch() {
+        let y = DVector::from_element(20, 1.0f64);
+        let x = DVector::from_element(19, 1.0f64);
+
+        let res = spread_adf(&y, &x, 1);
+        assert!(matches!(res, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_spread_adf_independent_walks() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let (x, y): (DVector<f64>, DVector<f64>) = gen_independent_walks(&mut rng, 200, 1.0);
+
+        let (_hedge_ratio, report) = spread_adf(&y, &x, 1).unwrap();
+        let p_value = engle_granger_p_value(report.test_statistic)
+            .unwrap()
+            .value();
+        assert!(p_value > 0.05);
+    }
+
+    #[test]
+    fn test_cointegration_matrix() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let n = 200;
+        let (x, y): (DVector<f64>, DVector<f64>) = gen_cointegrated_pair(&mut rng, n, 2.0, 0.5);
+        let (z, _) = gen_independent_walks(&mut rng, n, 1.0);
+
+        let p_values = cointegration_matrix(&[x.clone(), y.clone(), z], 1).unwrap();
+        assert_eq!(p_values.shape(), (3, 3));
+        for i in 0..3 {
+            assert!(p_values[(i, i)].is_nan());
+        }
+
+        // x and y are cointegrated, z is an independent random walk
+        assert!(p_values[(0, 1)] < 0.01);
+        assert!(p_values[(1, 0)] < 0.01);
+        assert!(p_values[(0, 2)] > 0.05);
+        assert!(p_values[(2, 1)] > 0.05);
+
+        let (_hedge_ratio, report) = spread_adf(&y, &x, 1).unwrap();
+        let expected = engle_granger_p_value(report.test_statistic)
+            .unwrap()
+            .value();
+        assert_eq!(p_values[(1, 0)], expected);
+    }
+}
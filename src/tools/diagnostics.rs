@@ -0,0 +1,528 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time series diagnostics
+use nalgebra::{DMatrix, DVector, RealField, Scalar};
+use num_traits::Float;
+
+use crate::prelude::tools::{Report, TestKind};
+use crate::Error;
+
+/// Returns the sample autocovariance of x at the given lag:
+/// $\gamma_k = \frac{1}{n} \sum_{t=k+1}^{n} (x_t - \bar{x})(x_{t-k} - \bar{x})$
+///
+/// The sum is normalized by n (not n - k) as in statsmodels.
+/// Returns zero if lag >= n.
+pub fn autocovariance<F: RealField + Scalar + Float>(x: &DVector<F>, lag: usize) -> F {
+    let n = x.len();
+    if lag >= n {
+        return F::zero();
+    }
+
+    let mean = x.mean();
+    let centered = x.add_scalar(-mean);
+
+    let head = centered.rows(lag, n - lag);
+    let tail = centered.rows(0, n - lag);
+
+    head.dot(&tail) / F::from(n).unwrap()
+}
+
+/// Returns the Newey-West long-run variance of x using a Bartlett kernel with the
+/// given bandwidth:
+/// $\hat{\sigma}^2 = \gamma_0 + 2 \sum_{k=1}^{L} \left(1 - \frac{k}{L + 1}\right)
+/// \gamma_k$
+///
+/// A bandwidth of 0 returns the sample variance (normalized by n).
+pub fn long_run_variance<F: RealField + Scalar + Float>(x: &DVector<F>, bandwidth: usize) -> F {
+    let two = F::from(2.0).unwrap();
+    let l = F::from(bandwidth + 1).unwrap();
+
+    (1..=bandwidth).fold(autocovariance(x, 0), |acc, k| {
+        let weight = F::one() - F::from(k).unwrap() / l;
+        acc + two * weight * autocovariance(x, k)
+    })
+}
+
+/// Kernel weighting the autocovariances of a long-run variance estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kernel {
+    /// $w(z) = 1 - |z|$ for $|z| \le 1$ - as [`long_run_variance`]
+    Bartlett,
+    /// $w(z) = 1 - 6z^2 + 6|z|^3$ for $|z| \le 1/2$, $2(1 - |z|)^3$ for
+    /// $1/2 < |z| \le 1$
+    Parzen,
+    /// $w(z) = \frac{25}{12\pi^2 z^2} \left(\frac{\sin(6\pi z/5)}{6\pi z/5} -
+    /// \cos(6\pi z/5)\right)$ - all autocovariances get a non-zero weight
+    QuadraticSpectral,
+}
+
+impl Kernel {
+    /// Returns the weight of the autocovariance at lag k for the given bandwidth.
+    /// Bartlett and Parzen use z = k / (bandwidth + 1) as the Newey-West estimator,
+    /// the quadratic spectral kernel z = k / bandwidth.
+    fn weight(&self, k: usize, bandwidth: usize) -> f64 {
+        match self {
+            Kernel::Bartlett => {
+                let z = k as f64 / (bandwidth + 1) as f64;
+                (1.0 - z).max(0.0)
+            }
+            Kernel::Parzen => {
+                let z = k as f64 / (bandwidth + 1) as f64;
+                if z <= 0.5 {
+                    1.0 - 6.0 * z * z + 6.0 * z * z * z
+                } else if z <= 1.0 {
+                    2.0 * (1.0 - z).powi(3)
+                } else {
+                    0.0
+                }
+            }
+            Kernel::QuadraticSpectral => {
+                if bandwidth == 0 {
+                    return 0.0;
+                }
+                let z = k as f64 / bandwidth as f64;
+                let a = 6.0 * std::f64::consts::PI * z / 5.0;
+                25.0 / (12.0 * std::f64::consts::PI.powi(2) * z * z) * (a.sin() / a - a.cos())
+            }
+        }
+    }
+}
+
+/// Returns the spectral density of x at frequency zero:
+/// $f(0) = \frac{1}{2\pi} \left(\gamma_0 + 2 \sum_{k \ge 1} w(k) \gamma_k\right)$
+/// i.e. the long-run variance up to a 2π factor, with the autocovariances weighted by
+/// the kernel - see [`Kernel`].
+///
+/// With `Kernel::Bartlett` this is [`long_run_variance`] / 2π.
+pub fn spectral_density_zero<F: RealField + Scalar + Float>(
+    x: &DVector<F>,
+    bandwidth: usize,
+    kernel: Kernel,
+) -> F {
+    let two = F::from(2.0).unwrap();
+    let two_pi = F::from(2.0 * std::f64::consts::PI).unwrap();
+
+    let max_lag = match kernel {
+        Kernel::Bartlett | Kernel::Parzen => bandwidth.min(x.len().saturating_sub(1)),
+        Kernel::QuadraticSpectral => x.len().saturating_sub(1),
+    };
+
+    let lrv = (1..=max_lag).fold(autocovariance(x, 0), |acc, k| {
+        let weight = F::from(kernel.weight(k, bandwidth)).unwrap();
+        acc + two * weight * autocovariance(x, k)
+    });
+
+    lrv / two_pi
+}
+
+/// Returns the Newey-West automatic bandwidth: $\lfloor 4 (n / 100)^{2/9} \rfloor$.
+pub fn newey_west_bandwidth(nobs: usize) -> usize {
+    (4.0 * (nobs as f64 / 100.0).powf(2.0 / 9.0)).floor() as usize
+}
+
+/// Returns the Andrews (1991) data-driven bandwidth for the Bartlett kernel using an
+/// AR(1) approximation of x:
+/// $\lfloor 1.1447 (\hat{\alpha}(1) n)^{1/3} \rfloor$ with
+/// $\hat{\alpha}(1) = \frac{4 \hat{\rho}^2}{(1 - \hat{\rho})^2 (1 + \hat{\rho})^2}$
+///
+/// The bandwidth is capped at n - 1.
+pub fn andrews_bandwidth<F: RealField + Scalar + Float>(x: &DVector<F>) -> usize {
+    let n = x.len();
+    if n < 2 {
+        return 0;
+    }
+
+    // least squares AR(1) coefficient of the demeaned series
+    let centered = x.add_scalar(-x.mean());
+    let head = centered.rows(1, n - 1);
+    let tail = centered.rows(0, n - 1);
+    let rho = (head.dot(&tail) / tail.dot(&tail)).to_f64().unwrap_or(0.0);
+
+    let alpha = 4.0 * rho * rho / ((1.0 - rho).powi(2) * (1.0 + rho).powi(2));
+    let bandwidth = 1.1447 * (alpha * n as f64).powf(1.0 / 3.0);
+
+    if bandwidth.is_finite() {
+        (bandwidth.floor() as usize).min(n - 1)
+    } else {
+        n - 1
+    }
+}
+
+/// Returns the sample autocorrelation function of x for lags 0 to max_lag included.
+/// The first element is always 1.
+pub fn acf<F: RealField + Scalar + Float>(x: &DVector<F>, max_lag: usize) -> DVector<F> {
+    let gamma_0 = autocovariance(x, 0);
+    DVector::from_fn(max_lag + 1, |k, _| autocovariance(x, k) / gamma_0)
+}
+
+/// Returns the sample partial autocorrelation function of x for lags 0 to max_lag
+/// included, computed from the autocorrelations with the Durbin-Levinson recursion.
+/// The first element is always 1.
+pub fn pacf<F: RealField + Scalar + Float>(x: &DVector<F>, max_lag: usize) -> DVector<F> {
+    let rho = acf(x, max_lag);
+
+    let mut pacf = DVector::from_element(max_lag + 1, F::one());
+    // phi[j - 1] is the j-th coefficient of the AR(k) fit
+    let mut phi: Vec<F> = Vec::with_capacity(max_lag);
+
+    for k in 1..=max_lag {
+        let mut num = rho[k];
+        let mut den = F::one();
+        for j in 1..k {
+            num -= phi[j - 1] * rho[k - j];
+            den -= phi[j - 1] * rho[j];
+        }
+        let phi_kk = num / den;
+
+        let previous = phi.clone();
+        for j in 1..k {
+            phi[j - 1] = previous[j - 1] - phi_kk * previous[k - j - 1];
+        }
+        phi.push(phi_kk);
+
+        pacf[k] = phi_kk;
+    }
+
+    pacf
+}
+
+/// 5% critical value of the supremum of the absolute value of a Brownian bridge, used as
+/// the boundary of the OLS-CUSUM test.
+const CUSUM_BOUNDARY_5PCT: f64 = 1.358;
+
+/// OLS-CUSUM structural stability test on the residuals of a regression.
+///
+/// Returns the path of the scaled cumulative sums of the residuals
+/// $B_t = \frac{1}{\hat{\sigma} \sqrt{n}} \sum_{j=1}^{t} e_j$ with
+/// $\hat{\sigma}^2 = \frac{1}{n} \sum e_j^2$, and the maximum of $|B_t|$ relative to the
+/// 5% boundary (1.358): a value above 1 means the path crosses the boundary lines and
+/// the stability of the relationship is rejected at the 5% level.
+///
+/// # References
+/// - Ploberger and Krämer (1992), The CUSUM test with OLS residuals. Econometrica 60(2).
+pub fn cusum_test<F: RealField + Scalar + Float>(
+    residuals: &DVector<F>,
+) -> Result<(DVector<F>, F), Error> {
+    let n = residuals.len();
+    if n < 2 {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let n_ = F::from(n).ok_or(Error::ConversionFailed)?;
+    let sigma = Float::sqrt(residuals.dot(residuals) / n_);
+    let scale = sigma * Float::sqrt(n_);
+
+    let mut sum = F::zero();
+    let path = residuals.map(|e| {
+        sum += e;
+        sum / scale
+    });
+
+    let boundary = F::from(CUSUM_BOUNDARY_5PCT).ok_or(Error::ConversionFailed)?;
+    let max_deviation = path.amax() / boundary;
+
+    Ok((path, max_deviation))
+}
+
+/// BDS test for independence of a series - typically the residuals of a fitted model.
+///
+/// The null hypothesis is that the series is IID. The test statistic is asymptotically
+/// standard normal under the null; large absolute values indicate (possibly non-linear)
+/// dependence.
+///
+/// - `m` is the embedding dimension and must satisfy 2 <= m < n.
+/// - `epsilon` is the distance within which two points are considered close. A common
+///   choice is 1.5 times the standard deviation of the series.
+///
+/// The returned size is the number of m-histories used, n - m + 1.
+///
+/// # References
+/// - Brock, Dechert, Scheinkman and LeBaron (1996), A test for independence based on the
+///   correlation dimension.
+/// - [Statsmodels](https://github.com/statsmodels/statsmodels/blob/main/statsmodels/tsa/stattools.py)
+pub fn bds_test<F: RealField + Scalar + Float>(
+    residuals: &DVector<F>,
+    m: usize,
+    epsilon: F,
+) -> Result<Report<F>, Error> {
+    let n = residuals.len();
+
+    if m < 2 {
+        return Err(Error::InvalidParameter(
+            "embedding dimension must be at least 2".into(),
+        ));
+    }
+    if m >= n || n < 3 {
+        return Err(Error::NotEnoughSamples);
+    }
+    if epsilon <= F::zero() {
+        return Err(Error::InvalidParameter("epsilon must be positive".into()));
+    }
+
+    // indicators[(i, j)] is 1 when |x_i - x_j| < epsilon
+    let indicators = DMatrix::from_fn(n, n, |i, j| {
+        if Float::abs(residuals[i] - residuals[j]) < epsilon {
+            F::one()
+        } else {
+            F::zero()
+        }
+    });
+
+    // correlation integral over the pairs i < j of an indicator matrix
+    let correlation_sum = |ind: &DMatrix<F>| -> Result<F, Error> {
+        let sz = ind.nrows();
+        let mut sum = F::zero();
+        for j in 1..sz {
+            for i in 0..j {
+                sum += ind[(i, j)];
+            }
+        }
+        let pairs = F::from(sz * (sz - 1) / 2).ok_or(Error::ConversionFailed)?;
+        Ok(sum / pairs)
+    };
+
+    // joint indicators of the m-histories
+    let mut joint = indicators.clone();
+    for _ in 1..m {
+        let sz = joint.nrows() - 1;
+        joint = DMatrix::from_fn(sz, sz, |i, j| joint[(i + 1, j + 1)] * indicators[(i, j)]);
+    }
+    let c_m = correlation_sum(&joint)?;
+
+    // variance under the null
+    let c_1 = correlation_sum(&indicators)?;
+    let n_ = F::from(n).ok_or(Error::ConversionFailed)?;
+    let row_sums_sq = indicators
+        .row_iter()
+        .fold(F::zero(), |acc, row| acc + Float::powi(row.sum(), 2));
+    let k = (row_sums_sq - F::from(3.0).unwrap() * indicators.sum() + F::from(2.0).unwrap() * n_)
+        / (n_ * (n_ - F::one()) * (n_ - F::from(2.0).unwrap()));
+
+    let m_ = F::from(m).ok_or(Error::ConversionFailed)?;
+    let mi = m as i32;
+    let tmp = (1..mi).fold(F::zero(), |acc, j| {
+        acc + Float::powi(k, mi - j) * Float::powi(c_1, 2 * j)
+    });
+    let variance = F::from(4.0).unwrap()
+        * (Float::powi(k, mi)
+            + F::from(2.0).unwrap() * tmp
+            + Float::powi(m_ - F::one(), 2) * Float::powi(c_1, 2 * mi)
+            - m_ * m_ * k * Float::powi(c_1, 2 * mi - 2));
+
+    // correlation integral of dimension 1 on the same observations as the m-histories
+    let size = n - m + 1;
+    let c_1_m = correlation_sum(&indicators.view((m - 1, m - 1), (size, size)).into_owned())?;
+
+    let effect = c_m - Float::powi(c_1_m, mi);
+    let size_ = F::from(size).ok_or(Error::ConversionFailed)?;
+    let test_statistic = Float::sqrt(size_) * effect / Float::sqrt(variance);
+
+    Ok(Report {
+        test_statistic,
+        size,
+        dropped_leading: m - 1,
+        kind: TestKind::Other,
+        regression: None,
+        df_resid: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use nalgebra::DVector;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::utils::gen_ar_1;
+
+    #[test]
+    fn test_autocovariance() {
+        let x = DVector::from_row_slice(&[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+
+        // centered: -2, -1, 0, 1, 2
+        assert_relative_eq!(autocovariance(&x, 0), 2.0, epsilon = 1e-12);
+        assert_relative_eq!(autocovariance(&x, 1), 0.8, epsilon = 1e-12);
+        assert_relative_eq!(autocovariance(&x, 2), -0.2, epsilon = 1e-12);
+        assert_relative_eq!(autocovariance(&x, 5), 0.0);
+    }
+
+    #[test]
+    fn test_long_run_variance() {
+        let x = DVector::from_row_slice(&[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+
+        assert_relative_eq!(long_run_variance(&x, 0), 2.0, epsilon = 1e-12);
+        // 2.0 + 2 * (1 - 1/3) * 0.8 + 2 * (1 - 2/3) * -0.2
+        assert_relative_eq!(
+            long_run_variance(&x, 2),
+            2.0 + 2.0 * (2.0 / 3.0) * 0.8 - 2.0 * (1.0 / 3.0) * 0.2,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_spectral_density_zero() {
+        let x = DVector::from_row_slice(&[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+        let two_pi = 2.0 * std::f64::consts::PI;
+
+        for bandwidth in 0..4 {
+            assert_relative_eq!(
+                spectral_density_zero(&x, bandwidth, Kernel::Bartlett) * two_pi,
+                long_run_variance(&x, bandwidth),
+                epsilon = 1e-12
+            );
+        }
+
+        // weights at z = 1/3 and 2/3: 1 - 6/9 + 6/27 and 2/27
+        assert_relative_eq!(
+            spectral_density_zero(&x, 2, Kernel::Parzen) * two_pi,
+            2.0 + 2.0 * (15.0 / 27.0) * 0.8 - 2.0 * (2.0 / 27.0) * 0.2,
+            epsilon = 1e-12
+        );
+
+        // the quadratic spectral weights decrease from 1 and reach every lag
+        let w = |k| Kernel::QuadraticSpectral.weight(k, 100);
+        assert_relative_eq!(w(1), 1.0, epsilon = 1e-3);
+        assert!(w(50) < w(1) && w(50) > 0.0);
+        assert!(Kernel::QuadraticSpectral.weight(4, 2) != 0.0);
+
+        assert_relative_eq!(
+            spectral_density_zero(&x, 0, Kernel::QuadraticSpectral) * two_pi,
+            2.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_bds() {
+        let x = DVector::from_row_slice(&[
+            -1.06714348f64,
+            -1.14700339,
+            0.79204106,
+            -0.05845247,
+            -0.67476754,
+            -0.10396661,
+            1.82059282,
+            -0.51169443,
+            2.07712365,
+            1.85668086,
+            2.56363688,
+        ]);
+
+        let report = bds_test(&x, 2, 1.0).unwrap();
+        assert_eq!(report.size, 10);
+        assert_relative_eq!(report.test_statistic, -1.6072768974649871, epsilon = 1e-9);
+
+        let report = bds_test(&x, 3, 1.5).unwrap();
+        assert_eq!(report.size, 9);
+        assert_relative_eq!(report.test_statistic, -0.8463978326688519, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_bds_invalid_parameters() {
+        let x = DVector::from_row_slice(&[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+
+        assert!(matches!(
+            bds_test(&x, 1, 1.0),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            bds_test(&x, 2, 0.0),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(bds_test(&x, 5, 1.0), Err(Error::NotEnoughSamples)));
+    }
+
+    #[test]
+    fn test_acf_pacf() {
+        let x = DVector::from_row_slice(&[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+
+        let rho = acf(&x, 2);
+        assert_relative_eq!(
+            rho,
+            DVector::from_row_slice(&[1.0, 0.4, -0.1]),
+            epsilon = 1e-12
+        );
+
+        let phi = pacf(&x, 2);
+        assert_relative_eq!(
+            phi,
+            DVector::from_row_slice(&[1.0, 0.4, -0.26 / 0.84]),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_acf_pacf_ar_1() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let delta = 0.7f64;
+        let y = gen_ar_1(&mut rng, 10000, 0.0, delta, 1.0);
+
+        // rho_k = delta^k
+        let rho = acf(&y, 3);
+        for k in 0..=3 {
+            assert_relative_eq!(rho[k], delta.powi(k as i32), epsilon = 0.05);
+        }
+
+        // the partial autocorrelations of an AR(1) vanish beyond lag 1
+        let phi = pacf(&y, 3);
+        assert_relative_eq!(phi[1], delta, epsilon = 0.05);
+        assert_relative_eq!(phi[2], 0.0, epsilon = 0.05);
+        assert_relative_eq!(phi[3], 0.0, epsilon = 0.05);
+    }
+
+    #[test]
+    fn test_newey_west_bandwidth() {
+        assert_eq!(newey_west_bandwidth(50), 3);
+        assert_eq!(newey_west_bandwidth(100), 4);
+        assert_eq!(newey_west_bandwidth(1000), 6);
+    }
+
+    #[test]
+    fn test_andrews_bandwidth() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        // rho = 0.5: alpha(1) = 16 / 9 -> 1.1447 * (16 / 9 * 10000)^(1/3) ~ 29.9
+        let y = gen_ar_1(&mut rng, 10000, 0.0f64, 0.5, 1.0);
+        let bandwidth = andrews_bandwidth(&y);
+        assert!((27..=33).contains(&bandwidth), "bandwidth: {}", bandwidth);
+
+        // white noise requires little correction
+        let y = gen_ar_1(&mut rng, 10000, 0.0f64, 0.0, 1.0);
+        assert!(andrews_bandwidth(&y) < 5);
+
+        let y = DVector::from_row_slice(&[1.0f64]);
+        assert_eq!(andrews_bandwidth(&y), 0);
+    }
+
+    #[test]
+    fn test_cusum() {
+        let residuals = DVector::from_row_slice(&[1.0f64, -1.0, 1.0, -1.0]);
+
+        let (path, max_deviation) = cusum_test(&residuals).unwrap();
+        // sigma = 1, scale = 2
+        assert_relative_eq!(path, DVector::from_row_slice(&[0.5, 0.0, 0.5, 0.0]));
+        assert_relative_eq!(max_deviation, 0.5 / 1.358, epsilon = 1e-12);
+
+        // a shift in the mean half way through
+        let residuals = DVector::from_fn(100, |i, _| if i < 50 { -1.0f64 } else { 1.0 });
+        let (_, max_deviation) = cusum_test(&residuals).unwrap();
+        assert!(max_deviation > 1.0);
+
+        assert!(cusum_test(&DVector::from_row_slice(&[1.0f64])).is_err());
+    }
+}
@@ -15,11 +15,12 @@
 use nalgebra::{RealField, Scalar};
 use num_traits::Float;
 
-use crate::distrib::Regression;
+use crate::distrib::dickeyfuller::get_critical_value;
+use crate::distrib::{AlphaLevel, Regression};
 use crate::prelude::nalgebra::DVector;
-use crate::prelude::tools::Report;
+use crate::prelude::tools::{Report, TestKind};
 use crate::regression::ols;
-use crate::tools::prepare;
+use crate::tools::{adf_min_length, prepare, UnitRootTest, LEVEL_COL};
 use crate::Error;
 
 /// Returns the t-statistic of the Dickey-Fuller test
@@ -32,11 +33,11 @@ use crate::Error;
 /// Critical values for can obtained from
 /// `unit_root::prelude::distrib::dickeyfuller::get_critical_value`.
 ///
-/// - If $t_{stat} < \mathrm{t_{\mathrm{crit}}(\alpha)}$ then reject $H_0$ at
-/// $alpha$ significance level - and thus conclude that the series is stationary.
+/// - If $t_{stat} < \mathrm{t_{\mathrm{crit}}(\alpha)}$ then reject $H_0$ at $alpha$
+///   significance level - and thus conclude that the series is stationary.
 /// - If $t_{stat} > \mathrm{t_{\mathrm{crit}}(\alpha)}$ then fail to reject $H_0$ at
-/// $alpha$ significance level - and thus conclude we cannot reject the hypothesis that
-/// the series is not stationary.
+///   $alpha$ significance level - and thus conclude we cannot reject the hypothesis that
+///   the series is not stationary.
 ///
 /// # Examples:
 ///
@@ -82,11 +83,76 @@ pub fn dickeyfuller_test<F: Float + Scalar + RealField>(
     let (_betas, t_stats) = ols(&delta_y, &y_t_1)?;
 
     Ok(Report {
-        test_statistic: t_stats[0],
+        test_statistic: t_stats[LEVEL_COL],
         size,
+        dropped_leading: 1,
+        kind: TestKind::DickeyFuller,
+        regression: Some(regression),
+        df_resid: Some(size - y_t_1.ncols()),
     })
 }
 
+/// Dickey-Fuller test configuration - see [`dickeyfuller_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DickeyFuller {
+    /// The deterministic terms of the regression
+    pub regression: Regression,
+}
+
+impl DickeyFuller {
+    /// Returns the minimum length of the series for the test to run: 3 plus the number
+    /// of deterministic terms.
+    pub fn min_length(&self) -> usize {
+        adf_min_length(0, self.regression)
+    }
+}
+
+impl<F: Float + Scalar + RealField> UnitRootTest<F> for DickeyFuller {
+    fn run(&self, y: &DVector<F>) -> Result<Report<F>, Error> {
+        dickeyfuller_test(y, self.regression)
+    }
+
+    fn min_length(&self) -> usize {
+        DickeyFuller::min_length(self)
+    }
+}
+
+/// Returns the Dickey-Fuller t-statistic from the estimated coefficient of y[t-1] and
+/// its standard error: `beta_level / se_level`.
+///
+/// Useful to check the pipeline against hand calculations or textbook worked examples
+/// - see [`df_is_stationary`] for the decision.
+pub fn df_statistic<F: Float>(beta_level: F, se_level: F) -> F {
+    beta_level / se_level
+}
+
+/// Returns whether the null hypothesis of a unit root is rejected at the given alpha
+/// level for a Dickey-Fuller t-statistic computed on a sample of the given size, i.e.
+/// whether the statistic is below the critical value.
+///
+/// # Examples:
+///
+/// ```rust
+/// use unit_root::prelude::distrib::{AlphaLevel, Regression};
+/// use unit_root::prelude::tools::{df_is_stationary, df_statistic};
+///
+/// let t_stat = df_statistic(-0.5f64, 0.1);
+/// assert!((t_stat - -5.0).abs() < 1e-12);
+///
+/// let stationary =
+///     df_is_stationary(t_stat, Regression::Constant, 50, AlphaLevel::FivePercent).unwrap();
+/// assert!(stationary);
+/// ```
+pub fn df_is_stationary<F: Float>(
+    statistic: F,
+    regression: Regression,
+    size: usize,
+    alpha: AlphaLevel,
+) -> Result<bool, Error> {
+    let critical_value: F = get_critical_value(regression, size, alpha)?;
+    Ok(statistic < critical_value)
+}
+
 /// Comparison with statsmodels.tsa.stattools.adfuller use the following code - see
 /// [`tools::adf_test::test`] for the definition of the function:
 /// ```python
@@ -103,7 +169,6 @@ mod tests {
 
     use super::*;
     use crate::distrib::dickeyfuller::constant_no_trend_critical_value;
-    use crate::distrib::AlphaLevel;
     use crate::utils::gen_ar_1;
 
     const Y: [f64; 11] = [
@@ -182,11 +247,8 @@ mod tests {
 
         let report = dickeyfuller_test(&y, Regression::Constant).unwrap();
 
-        let critical_value =
-            match constant_no_trend_critical_value(report.size, AlphaLevel::OnePercent) {
-                Ok(v) => v,
-                Err(_) => f32::MIN,
-            };
+        let critical_value = constant_no_trend_critical_value(report.size, AlphaLevel::OnePercent)
+            .unwrap_or(f32::MIN);
 
         let t_stat = report.test_statistic;
         assert!(t_stat < critical_value);
@@ -203,11 +265,8 @@ mod tests {
 
         let report = dickeyfuller_test(&y, Regression::Constant).unwrap();
 
-        let critical_value =
-            match constant_no_trend_critical_value(report.size, AlphaLevel::OnePercent) {
-                Ok(v) => v,
-                Err(_) => f32::MAX,
-            };
+        let critical_value = constant_no_trend_critical_value(report.size, AlphaLevel::OnePercent)
+            .unwrap_or(f32::MAX);
 
         let t_stat = report.test_statistic;
         assert!(t_stat > critical_value);
@@ -224,11 +283,8 @@ mod tests {
 
         let report = dickeyfuller_test(&y, Regression::Constant).unwrap();
 
-        let critical_value =
-            match constant_no_trend_critical_value(report.size, AlphaLevel::OnePercent) {
-                Ok(v) => v,
-                Err(_) => f64::MIN,
-            };
+        let critical_value = constant_no_trend_critical_value(report.size, AlphaLevel::OnePercent)
+            .unwrap_or(f64::MIN);
 
         let t_stat = report.test_statistic;
         assert!(t_stat < critical_value);
@@ -245,11 +301,8 @@ mod tests {
 
         let report = dickeyfuller_test(&y, Regression::Constant).unwrap();
 
-        let critical_value =
-            match constant_no_trend_critical_value(report.size, AlphaLevel::OnePercent) {
-                Ok(v) => v,
-                Err(_) => f64::MAX,
-            };
+        let critical_value = constant_no_trend_critical_value(report.size, AlphaLevel::OnePercent)
+            .unwrap_or(f64::MAX);
 
         let t_stat = report.test_statistic;
         assert!(t_stat > critical_value);
@@ -272,4 +325,27 @@ mod tests {
 
         assert_eq!(report.size, 9);
     }
+
+    #[test]
+    fn test_df_statistic_from_estimators() {
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let (delta_y, x, size) = prepare(&y, 0, Regression::Constant).unwrap();
+        let (betas, t_stats) = ols(&delta_y, &x).unwrap();
+        let se = betas[LEVEL_COL] / t_stats[LEVEL_COL];
+
+        let t_stat = df_statistic(betas[LEVEL_COL], se);
+        assert_relative_eq!(t_stat, -1.83288396527f64, epsilon = 1e-9);
+
+        let report = dickeyfuller_test(&y, Regression::Constant).unwrap();
+        for alpha in [AlphaLevel::OnePercent, AlphaLevel::TenPercent] {
+            assert_eq!(
+                df_is_stationary(t_stat, Regression::Constant, size, alpha).unwrap(),
+                report.is_stationary(alpha).unwrap()
+            );
+        }
+        assert!(
+            df_is_stationary(-10.0, Regression::Constant, size, AlphaLevel::OnePercent).unwrap()
+        );
+    }
 }
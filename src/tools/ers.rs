@@ -0,0 +1,218 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Elliott-Rothenberg-Stock point-optimal test
+use nalgebra::{DMatrix, DVector, RealField, Scalar};
+use num_traits::Float;
+
+use crate::distrib::Regression;
+use crate::prelude::tools::{Report, TestKind};
+use crate::regression::{ols, residuals};
+use crate::tools::{add_deterministic_terms, adf_min_length, prepare, UnitRootTest, LEVEL_COL};
+use crate::Error;
+
+/// Returns the local-to-unity parameter c̄ of the GLS detrending for the regression.
+pub(crate) fn gls_c_bar(regression: Regression) -> Result<f64, Error> {
+    match regression {
+        Regression::Constant => Ok(-7.0),
+        Regression::ConstantAndTrend => Ok(-13.5),
+        Regression::NoConstantNoTrend => Err(Error::InvalidParameter(
+            "GLS detrending requires a constant".into(),
+        )),
+    }
+}
+
+/// Returns the quasi-difference of the rows of m: (m_1, m_2 - a m_1, ..., m_n - a
+/// m_{n-1}).
+fn quasi_difference<F: RealField + Scalar + Float>(m: &DMatrix<F>, a: F) -> DMatrix<F> {
+    let mut out = m.clone();
+    for i in (1..m.nrows()).rev() {
+        let row = m.row(i) - m.row(i - 1) * a;
+        out.set_row(i, &row);
+    }
+    out
+}
+
+/// GLS regression of y on the deterministic terms of the regression after
+/// quasi-differencing with a = 1 + c̄ / n.
+/// Returns the coefficients of the deterministic terms and the sum of squared residuals
+/// of the quasi-differenced regression.
+pub(crate) fn gls_regression<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    regression: Regression,
+    a: F,
+) -> Result<(DVector<F>, F), Error> {
+    let mut z = DMatrix::zeros(y.len(), 0);
+    add_deterministic_terms(&mut z, regression)?;
+
+    let y_a = quasi_difference(&DMatrix::from_column_slice(y.len(), 1, y.as_slice()), a)
+        .column(0)
+        .into_owned();
+    let z_a = quasi_difference(&z, a);
+
+    let (betas, _t_stats) = ols(&y_a, &z_a)?;
+    let residuals = residuals(&y_a, &z_a, &betas);
+
+    Ok((betas, residuals.dot(&residuals)))
+}
+
+/// Elliott-Rothenberg-Stock point-optimal unit root test
+///
+/// Returns the P_T statistic:
+/// $P_T = \frac{S(\bar{a}) - \bar{a} S(1)}{\hat{\omega}^2}$
+/// where $S(a)$ is the sum of squared residuals of the GLS regression of y on the
+/// deterministic terms after quasi-differencing with $a$, $\bar{a} = 1 + \bar{c} / n$
+/// with $\bar{c} = -7$ (constant) or $-13.5$ (constant and trend), and
+/// $\hat{\omega}^2$ is the autoregressive spectral density estimate at frequency zero
+/// from the ADF regression with `lag` augmenting lags.
+///
+/// The null hypothesis is that the series has a unit root; it is rejected for small
+/// values of the statistic. Critical values are given by
+/// `distrib::ers::ers_critical_value` - not by the Dickey-Fuller tables: the report is of
+/// kind `TestKind::Ers` and its Dickey-Fuller methods return an error.
+///
+/// `Regression::NoConstantNoTrend` is not supported.
+///
+/// # References
+/// - Elliott, Rothenberg and Stock (1996), Efficient Tests for an Autoregressive Unit
+///   Root. Econometrica 64(4).
+pub fn ers_point_optimal<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    regression: Regression,
+    lag: usize,
+) -> Result<Report<F>, Error> {
+    let c_bar = gls_c_bar(regression)?;
+    let n = y.len();
+
+    let (delta_y, x, size) = prepare(y, lag, regression)?;
+
+    let a = F::from(1.0 + c_bar / n as f64).ok_or(Error::ConversionFailed)?;
+    let (_, ssr_a) = gls_regression(y, regression, a)?;
+    let (_, ssr_1) = gls_regression(y, regression, F::one())?;
+
+    // spectral density at frequency zero from the ADF regression
+    let (betas, _t_stats) = ols(&delta_y, &x)?;
+    let residuals = residuals(&delta_y, &x, &betas);
+    let sigma2 = residuals.dot(&residuals) / F::from(size).ok_or(Error::ConversionFailed)?;
+    let lags_sum = betas.rows(LEVEL_COL + 1, lag).sum();
+    let omega2 = sigma2 / Float::powi(F::one() - lags_sum, 2);
+
+    Ok(Report {
+        test_statistic: (ssr_a - a * ssr_1) / omega2,
+        size: n,
+        dropped_leading: 0,
+        kind: TestKind::Ers,
+        regression: Some(regression),
+        df_resid: None,
+    })
+}
+
+/// Elliott-Rothenberg-Stock point-optimal test configuration - see
+/// [`ers_point_optimal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ers {
+    /// The deterministic terms of the regression
+    pub regression: Regression,
+    /// The number of augmenting lags of the spectral density estimate
+    pub lag: usize,
+}
+
+impl Ers {
+    /// Returns the minimum length of the series for the test to run: that of the
+    /// Augmented Dickey-Fuller regression of the spectral density estimate.
+    pub fn min_length(&self) -> usize {
+        adf_min_length(self.lag, self.regression)
+    }
+}
+
+impl<F: RealField + Scalar + Float> UnitRootTest<F> for Ers {
+    fn run(&self, y: &DVector<F>) -> Result<Report<F>, Error> {
+        ers_point_optimal(y, self.regression, self.lag)
+    }
+
+    fn min_length(&self) -> usize {
+        Ers::min_length(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::DVector;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::distrib::ers::ers_critical_value;
+    use crate::distrib::AlphaLevel;
+    use crate::utils::gen_ar_1;
+
+    #[test]
+    fn test_ers_no_unit_root() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1(&mut rng, 200, 0.0f64, 0.5, 1.0);
+
+        for regression in [Regression::Constant, Regression::ConstantAndTrend] {
+            let report = ers_point_optimal(&y, regression, 1).unwrap();
+            let critical_value: f64 =
+                ers_critical_value(regression, report.size, AlphaLevel::OnePercent).unwrap();
+
+            assert_eq!(report.size, 200);
+            assert!(report.test_statistic < critical_value);
+        }
+    }
+
+    #[test]
+    fn test_ers_with_unit_root() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let y = gen_ar_1(&mut rng, 200, 0.0f64, 1.0, 1.0);
+
+        for regression in [Regression::Constant, Regression::ConstantAndTrend] {
+            let report = ers_point_optimal(&y, regression, 1).unwrap();
+            let critical_value: f64 =
+                ers_critical_value(regression, report.size, AlphaLevel::TenPercent).unwrap();
+
+            assert!(report.test_statistic > critical_value);
+        }
+    }
+
+    #[test]
+    fn test_ers_report_is_not_dickeyfuller() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1(&mut rng, 200, 0.0f64, 0.5, 1.0);
+
+        let report = ers_point_optimal(&y, Regression::Constant, 1).unwrap();
+        assert_eq!(report.kind, TestKind::Ers);
+        assert_eq!(report.regression, Some(Regression::Constant));
+
+        // the P_T statistic is not compared with the Dickey-Fuller tau tables
+        assert!(matches!(
+            report.critical_values(),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            report.is_stationary(AlphaLevel::FivePercent),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(report.decisions().is_err());
+        assert!(report.effective_alpha().is_err());
+        assert!(report.margin(AlphaLevel::FivePercent).is_err());
+        assert!(report.to_row("y", 1, AlphaLevel::FivePercent).is_err());
+    }
+
+    #[test]
+    fn test_ers_no_constant() {
+        let y = DVector::from_row_slice(&[1.0f64, 2.0, 3.0, 2.0, 1.0, 2.0]);
+        assert!(ers_point_optimal(&y, Regression::NoConstantNoTrend, 0).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tabular export of test reports
+use std::fmt::Debug;
+#[cfg(feature = "csv")]
+use std::fmt::Display;
+#[cfg(feature = "csv")]
+use std::io::Write;
+
+use num_traits::Float;
+
+use crate::distrib::mackinnon::mackinnon_p_value;
+use crate::distrib::AlphaLevel;
+use crate::prelude::tools::Report;
+use crate::tools::TestDecision;
+use crate::Error;
+
+/// Flat summary of an (Augmented) Dickey-Fuller report on one series, e.g. a line of the
+/// output of a screening of many series - see [`Report::to_row`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRow<F> {
+    /// The identifier of the series
+    pub series_id: String,
+    /// The test statistic
+    pub statistic: F,
+    /// The MacKinnon (1994) approximate p-value, or the bound of the fitted region it
+    /// lies beyond - see `distrib::PValue::value`.
+    pub p_value: F,
+    /// The number of augmenting lags
+    pub used_lag: usize,
+    /// The number of observations used in the test regression - see
+    /// [`Report::nobs_statsmodels`].
+    pub nobs: usize,
+    /// The alpha level of the decision
+    pub alpha: AlphaLevel,
+    /// The decision on the unit root null at alpha
+    pub decision: TestDecision,
+}
+
+impl<F: Debug + Clone + Float> Report<F> {
+    /// Returns the row summarizing this (Augmented) Dickey-Fuller report on the series
+    /// series_id with used_lag augmenting lags, with the decision at alpha.
+    ///
+    /// Returns `Error::InvalidParameter` if this is not a Dickey-Fuller report.
+    pub fn to_row(
+        &self,
+        series_id: impl Into<String>,
+        used_lag: usize,
+        alpha: AlphaLevel,
+    ) -> Result<ReportRow<F>, Error> {
+        let regression = self.dickeyfuller_regression()?;
+
+        let decision = if self.is_stationary(alpha)? {
+            TestDecision::Reject
+        } else {
+            TestDecision::FailToReject
+        };
+
+        Ok(ReportRow {
+            series_id: series_id.into(),
+            statistic: self.test_statistic,
+            p_value: mackinnon_p_value(self.test_statistic, regression)?.value(),
+            used_lag,
+            nobs: self.nobs_statsmodels(),
+            alpha,
+            decision,
+        })
+    }
+}
+
+/// Writes the rows as CSV with a header line:
+/// `series_id,statistic,p_value,used_lag,nobs,alpha,decision`.
+///
+/// The series identifiers are quoted when needed, as in RFC 4180, and the decision is
+/// written as `reject` or `fail_to_reject`.
+#[cfg(feature = "csv")]
+pub fn write_reports<W: Write, F: Display>(
+    mut writer: W,
+    rows: &[ReportRow<F>],
+) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "series_id,statistic,p_value,used_lag,nobs,alpha,decision"
+    )?;
+
+    for row in rows {
+        let decision = match row.decision {
+            TestDecision::Reject => "reject",
+            TestDecision::FailToReject => "fail_to_reject",
+        };
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            escape(&row.series_id),
+            row.statistic,
+            row.p_value,
+            row.used_lag,
+            row.nobs,
+            row.alpha.value(),
+            decision
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Returns the field quoted, with its quotes doubled, if it contains a comma, a quote
+/// or a line break.
+#[cfg(feature = "csv")]
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::DVector;
+
+    use crate::distrib::{AlphaLevel, Regression};
+    use crate::tools::adf::adf_test;
+    use crate::tools::TestDecision;
+    use crate::Error;
+
+    const Y: [f64; 11] = [
+        -1.06714348,
+        -1.14700339,
+        0.79204106,
+        -0.05845247,
+        -0.67476754,
+        -0.10396661,
+        1.82059282,
+        -0.51169443,
+        2.07712365,
+        1.85668086,
+        2.56363688,
+    ];
+
+    #[test]
+    fn test_report_to_row() {
+        let y = DVector::from_row_slice(&Y[..]);
+        let report = adf_test(&y, 0, Regression::ConstantAndTrend).unwrap();
+
+        let row = report.to_row("Y", 0, AlphaLevel::FivePercent).unwrap();
+        assert_eq!(row.series_id, "Y");
+        assert_eq!(row.statistic, report.test_statistic);
+        // statsmodels: p-value 0.00442477220907
+        assert!((row.p_value - 0.00442477220907).abs() < 1e-6);
+        assert_eq!(row.used_lag, 0);
+        assert_eq!(row.nobs, 10);
+        assert_eq!(row.decision, TestDecision::Reject);
+
+        let report = crate::tools::Report {
+            regression: None,
+            ..report
+        };
+        assert!(matches!(
+            report.to_row("Y", 0, AlphaLevel::FivePercent),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_write_reports() {
+        let row = super::ReportRow {
+            series_id: "a,\"b\"".to_string(),
+            statistic: -4.5f64,
+            p_value: 0.001,
+            used_lag: 2,
+            nobs: 97,
+            alpha: AlphaLevel::FivePercent,
+            decision: TestDecision::Reject,
+        };
+        let other = super::ReportRow {
+            series_id: "c".to_string(),
+            statistic: -1.0,
+            p_value: 0.75,
+            used_lag: 0,
+            nobs: 99,
+            alpha: AlphaLevel::FivePercent,
+            decision: TestDecision::FailToReject,
+        };
+
+        let mut out = Vec::new();
+        super::write_reports(&mut out, &[row, other]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "series_id,statistic,p_value,used_lag,nobs,alpha,decision\n\
+             \"a,\"\"b\"\"\",-4.5,0.001,2,97,0.05,reject\n\
+             c,-1,0.75,0,99,0.05,fail_to_reject\n"
+        );
+    }
+}
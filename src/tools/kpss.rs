@@ -0,0 +1,185 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Kwiatkowski-Phillips-Schmidt-Shin stationarity test
+use nalgebra::{DMatrix, DVector, RealField, Scalar};
+use num_traits::Float;
+
+use crate::distrib::Regression;
+use crate::prelude::tools::{Report, TestKind};
+use crate::regression::{ols, residuals};
+use crate::tools::diagnostics::{long_run_variance, newey_west_bandwidth};
+use crate::tools::{add_deterministic_terms, deterministic_terms, UnitRootTest};
+use crate::Error;
+
+/// Kwiatkowski-Phillips-Schmidt-Shin stationarity test
+///
+/// Returns the statistic:
+/// $\eta = \frac{\sum_{t=1}^{n} S_t^2}{n^2 \hat{\sigma}^2}$
+/// where $S_t$ is the partial sum of the residuals of the regression of y on the
+/// deterministic terms and $\hat{\sigma}^2$ their Newey-West long-run variance with
+/// the given bandwidth - see `diagnostics::long_run_variance`.
+///
+/// Unlike the Dickey-Fuller tests, the null hypothesis is that the series is (level or
+/// trend) stationary; it is rejected for large values of the statistic. Critical values
+/// are given by `distrib::kpss::kpss_critical_value` - not by the Dickey-Fuller tables:
+/// the report is of kind `TestKind::Kpss` and its Dickey-Fuller methods return an error.
+///
+/// `Regression::NoConstantNoTrend` is not supported.
+///
+/// # References
+/// - Kwiatkowski, Phillips, Schmidt and Shin (1992), Testing the null hypothesis of
+///   stationarity against the alternative of a unit root. Journal of Econometrics 54.
+/// - [Statsmodels](https://github.com/statsmodels/statsmodels/blob/main/statsmodels/tsa/stattools.py)
+pub fn kpss_test<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    regression: Regression,
+    bandwidth: usize,
+) -> Result<Report<F>, Error> {
+    if regression == Regression::NoConstantNoTrend {
+        return Err(Error::InvalidParameter(
+            "the KPSS test requires a constant".into(),
+        ));
+    }
+
+    let n = y.len();
+
+    let mut x = DMatrix::zeros(n, 0);
+    add_deterministic_terms(&mut x, regression)?;
+
+    let (betas, _t_stats) = ols(y, &x)?;
+    let residuals = residuals(y, &x, &betas);
+
+    let mut partial_sum = F::zero();
+    let eta = residuals.iter().fold(F::zero(), |acc, e| {
+        partial_sum += *e;
+        acc + partial_sum * partial_sum
+    });
+    let n_f = F::from(n).ok_or(Error::ConversionFailed)?;
+
+    Ok(Report {
+        test_statistic: eta / (n_f * n_f * long_run_variance(&residuals, bandwidth)),
+        size: n,
+        dropped_leading: 0,
+        kind: TestKind::Kpss,
+        regression: Some(regression),
+        df_resid: None,
+    })
+}
+
+/// Kwiatkowski-Phillips-Schmidt-Shin test configuration - see [`kpss_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Kpss {
+    /// The deterministic terms of the regression
+    pub regression: Regression,
+    /// The bandwidth of the long-run variance estimate, or `None` for the Newey-West
+    /// automatic bandwidth of the length of the series - see
+    /// `diagnostics::newey_west_bandwidth`.
+    pub bandwidth: Option<usize>,
+}
+
+impl Kpss {
+    /// Returns the configuration of the test with the Newey-West automatic bandwidth.
+    pub fn new(regression: Regression) -> Self {
+        Kpss {
+            regression,
+            bandwidth: None,
+        }
+    }
+
+    /// Returns the minimum length of the series for the test to run: one more than the
+    /// number of deterministic terms.
+    pub fn min_length(&self) -> usize {
+        deterministic_terms(self.regression) + 1
+    }
+}
+
+impl<F: RealField + Scalar + Float> UnitRootTest<F> for Kpss {
+    fn run(&self, y: &DVector<F>) -> Result<Report<F>, Error> {
+        let bandwidth = self
+            .bandwidth
+            .unwrap_or_else(|| newey_west_bandwidth(y.len()));
+        kpss_test(y, self.regression, bandwidth)
+    }
+
+    fn min_length(&self) -> usize {
+        Kpss::min_length(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::distrib::AlphaLevel;
+
+    const Y: [f64; 11] = [
+        -1.06714348,
+        -1.14700339,
+        0.79204106,
+        -0.05845247,
+        -0.67476754,
+        -0.10396661,
+        1.82059282,
+        -0.51169443,
+        2.07712365,
+        1.85668086,
+        2.56363688,
+    ];
+
+    /// Expected values follow statsmodels.tsa.stattools.kpss(y, regression, nlags=2).
+    #[test]
+    fn test_kpss_test() {
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let report = kpss_test(&y, Regression::Constant, 2).unwrap();
+        assert_eq!(report.size, 11);
+        assert_relative_eq!(report.test_statistic, 0.471238000478, epsilon = 1e-9);
+
+        let report = Kpss {
+            regression: Regression::ConstantAndTrend,
+            bandwidth: Some(2),
+        }
+        .run(&y)
+        .unwrap();
+        assert_relative_eq!(report.test_statistic, 0.134386060557, epsilon = 1e-9);
+
+        // the default bandwidth is the Newey-West one: 2 for 11 observations
+        let report = Kpss::new(Regression::ConstantAndTrend).run(&y).unwrap();
+        assert_eq!(newey_west_bandwidth(y.len()), 2);
+        assert_relative_eq!(report.test_statistic, 0.134386060557, epsilon = 1e-9);
+
+        assert!(kpss_test(&y, Regression::NoConstantNoTrend, 2).is_err());
+    }
+
+    #[test]
+    fn test_kpss_report_is_not_dickeyfuller() {
+        let y = DVector::from_row_slice(&Y[..]);
+
+        let report = kpss_test(&y, Regression::Constant, 2).unwrap();
+        assert_eq!(report.kind, TestKind::Kpss);
+
+        // the right-tailed eta statistic is not compared with the Dickey-Fuller tau tables
+        assert!(matches!(
+            report.critical_values(),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(report.is_stationary(AlphaLevel::FivePercent).is_err());
+        assert!(report.decisions().is_err());
+        assert!(report.effective_alpha().is_err());
+        assert!(report.margin(AlphaLevel::FivePercent).is_err());
+        assert!(report.to_row("y", 0, AlphaLevel::FivePercent).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistence of mean-reverting series
+use nalgebra::{DVector, RealField, Scalar};
+use num_traits::Float;
+
+use crate::distrib::Regression;
+use crate::regression::ols;
+use crate::tools::{prepare, LEVEL_COL};
+use crate::Error;
+
+/// Returns the AR(1) root 1 + β_1 implied by the coefficient β_1 of y[t-1] in the
+/// (Augmented) Dickey-Fuller regression Δy_t = β_1 y_{t-1} + ...
+pub fn ar_root<F: Float>(beta_level: F) -> F {
+    F::one() + beta_level
+}
+
+/// Returns whether the AR(1) root lies strictly inside the unit circle, i.e. whether
+/// the estimated process is mean-reverting.
+pub fn is_within_unit_circle<F: Float>(root: F) -> bool {
+    root.abs() < F::one()
+}
+
+/// Returns the AR(1) root of y estimated from the Augmented Dickey-Fuller regression
+/// with the given lag and regression - see [`ar_root`].
+///
+/// This is the estimated persistence behind the test verdict: the closer to 1, the
+/// slower the mean reversion.
+pub fn estimated_ar_root<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<F, Error> {
+    let (delta_y, x, _size) = prepare(y, lag, regression)?;
+
+    let (betas, _t_stats) = ols(&delta_y, &x)?;
+
+    Ok(ar_root(betas[LEVEL_COL]))
+}
+
+/// Returns the AR(1) coefficient corrected for the finite-sample bias of its OLS
+/// estimate rho_hat on a series of nobs observations, e.g. the root returned by
+/// [`estimated_ar_root`] with `Regression::Constant`.
+///
+/// With an estimated mean, Kendall (1954) gives the bias
+/// $E[\hat{\rho}] - \rho \approx -(1 + 3\rho) / n$, which is inverted into
+/// $\tilde{\rho} = (n \hat{\rho} + 1) / (n - 3)$.
+///
+/// The approximation is first order in 1/n for a stationary AR(1): it is reliable for
+/// |rho| clearly below 1 and n of a few dozen observations or more. Close to a unit root
+/// it overcorrects and may exceed 1. Returns NaN if nobs <= 3.
+///
+/// # References
+/// - Kendall, M.G. (1954). Note on bias in the estimation of autocorrelation. Biometrika
+///   41.
+pub fn kendall_bias_correction<F: Float>(rho_hat: F, nobs: usize) -> F {
+    if nobs <= 3 {
+        return F::nan();
+    }
+
+    let n = F::from(nobs).unwrap_or_else(F::nan);
+    let three = F::from(3.0).unwrap_or_else(F::nan);
+
+    (n * rho_hat + F::one()) / (n - three)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::utils::gen_ar_1;
+
+    #[test]
+    fn test_ar_root() {
+        assert_relative_eq!(ar_root(-0.3f64), 0.7);
+        assert!(is_within_unit_circle(ar_root(-0.3f64)));
+        assert!(!is_within_unit_circle(ar_root(0.0f64)));
+        assert!(!is_within_unit_circle(ar_root(-2.5f64)));
+    }
+
+    #[test]
+    fn test_kendall_bias_correction() {
+        // (50 * 0.5 + 1) / 47
+        assert_relative_eq!(kendall_bias_correction(0.5f64, 50), 26.0 / 47.0);
+        assert!(kendall_bias_correction(0.5f64, 3).is_nan());
+
+        // the OLS estimate is biased downwards, the corrected one much less so
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let (n, delta, reps) = (50, 0.5, 1000);
+
+        let (mut ols_mean, mut corrected_mean) = (0.0, 0.0);
+        for _ in 0..reps {
+            let y: DVector<f64> = gen_ar_1(&mut rng, n, 0.0, delta, 1.0);
+            let root = estimated_ar_root(&y, 0, Regression::Constant).unwrap();
+            ols_mean += root / reps as f64;
+            corrected_mean += kendall_bias_correction(root, n) / reps as f64;
+        }
+
+        assert!(delta - ols_mean > 0.02);
+        assert!((corrected_mean - delta).abs() < (ols_mean - delta).abs() / 3.0);
+    }
+
+    #[test]
+    fn test_estimated_ar_root() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let delta = 0.7;
+        let y: DVector<f64> = gen_ar_1(&mut rng, 2000, 0.0, delta, 1.0);
+
+        let root = estimated_ar_root(&y, 0, Regression::Constant).unwrap();
+        assert_relative_eq!(root, delta, epsilon = 0.05);
+        assert!(is_within_unit_circle(root));
+    }
+}
@@ -1,9 +1,11 @@
 use std::fmt::Debug;
 
-use nalgebra::{DMatrix, DVector, RealField, Scalar};
+use nalgebra::{DMatrix, DMatrixView, DVector, DVectorView, RealField, Scalar};
 use num_traits::Float;
 
-use crate::distrib::Regression;
+use crate::distrib::dickeyfuller::get_critical_value;
+use crate::distrib::kpss::kpss_critical_value;
+use crate::distrib::{AlphaLevel, Deterministics, Regression};
 use crate::Error;
 
 // Copyright (c) 2022. Sebastien Soudan
@@ -20,24 +22,386 @@ use crate::Error;
 // See the License for the specific language governing permissions and
 // limitations under the License.
 pub(crate) mod adf;
+pub(crate) mod cointegration;
+pub(crate) mod diagnostics;
 pub(crate) mod dickeyfuller;
+pub(crate) mod ers;
+pub(crate) mod export;
+pub(crate) mod kpss;
+pub(crate) mod meanrev;
+pub(crate) mod online;
+pub(crate) mod panel;
+pub(crate) mod series;
+
+/// Index of the y[t-1] (level) column in the design matrix built by [`prepare`],
+/// and thus of its coefficient in the regression output.
+pub(crate) const LEVEL_COL: usize = 0;
+
+/// Family of the statistic of a [`Report`], i.e. the distribution its critical values
+/// come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestKind {
+    /// The (Augmented) Dickey-Fuller tau statistic: left-tailed, with the critical
+    /// values of `distrib::dickeyfuller` and the p-values of `distrib::mackinnon`.
+    DickeyFuller,
+    /// The KPSS eta statistic: right-tailed, with the critical values of
+    /// `distrib::kpss`.
+    Kpss,
+    /// The Elliott-Rothenberg-Stock point-optimal P_T statistic: left-tailed, with the
+    /// critical values of `distrib::ers`.
+    Ers,
+    /// Any other statistic, e.g. a t-statistic whose Dickey-Fuller critical values do
+    /// not apply.
+    Other,
+}
 
 /// Test report
 #[derive(Debug, Clone)]
 pub struct Report<F: Debug + Clone> {
     /// The test statistic
     pub test_statistic: F,
-    /// The size of the sample
+    /// The size of the sample: the number of observations used in the test regression,
+    /// i.e. len(y) - lag - 1 for the (Augmented) Dickey-Fuller test as the first
+    /// difference and the lags consume lag + 1 observations.
+    ///
+    /// This is the size to pass to the critical value functions of `distrib` - see
+    /// [`Report::nobs_statsmodels`] for the comparison with statsmodels.
     pub size: usize,
+    /// The number of leading observations of the series not used in the test
+    /// regression, e.g. lag + 1 for the (Augmented) Dickey-Fuller test, so that
+    /// len(y) = dropped_leading + size.
+    pub dropped_leading: usize,
+    /// The family of the test statistic.
+    ///
+    /// The Dickey-Fuller methods of the report - [`Report::critical_values`],
+    /// [`Report::is_stationary`], ... - only accept `TestKind::DickeyFuller`.
+    pub kind: TestKind,
+    /// The regression used to compute the test statistic.
+    /// `None` for tests that do not involve a deterministic specification.
+    pub regression: Option<Regression>,
+    /// The residual degrees of freedom of the test regression: n - k.
+    /// `None` for tests that are not regression-based.
+    pub df_resid: Option<usize>,
+}
+
+impl<F: Debug + Clone + Float> Report<F> {
+    /// Returns the regression of this report if it is a Dickey-Fuller report.
+    ///
+    /// Returns `Error::InvalidParameter` for another kind of test, or if the report does
+    /// not carry a regression.
+    pub(crate) fn dickeyfuller_regression(&self) -> Result<Regression, Error> {
+        if self.kind != TestKind::DickeyFuller {
+            return Err(Error::InvalidParameter(format!(
+                "the Dickey-Fuller tables do not apply to a {:?} report",
+                self.kind
+            )));
+        }
+
+        self.regression
+            .ok_or_else(|| Error::InvalidParameter("report does not carry a regression".into()))
+    }
+
+    /// Returns the Dickey-Fuller critical values for the regression and size of this
+    /// report, for each alpha level from the most to the least stringent.
+    ///
+    /// Returns `Error::InvalidParameter` if this is not a Dickey-Fuller report and
+    /// `Error::SampleSizeOutOfRange` if its size is below
+    /// [`MIN_SAMPLE_SIZE`](crate::distrib::dickeyfuller::MIN_SAMPLE_SIZE), i.e. 10.
+    pub fn critical_values(&self) -> Result<Vec<(AlphaLevel, F)>, Error> {
+        self.critical_values_iter()
+            .map(|(alpha, critical_value)| Ok((alpha, critical_value?)))
+            .collect()
+    }
+
+    /// Returns an iterator over the Dickey-Fuller critical values for the regression and
+    /// size of this report, for each alpha level from the most to the least stringent.
+    ///
+    /// Every item is an error if this is not a Dickey-Fuller report.
+    pub fn critical_values_iter(
+        &self,
+    ) -> impl Iterator<Item = (AlphaLevel, Result<F, Error>)> + '_ {
+        let regression = self.dickeyfuller_regression();
+
+        AlphaLevel::all().into_iter().map(move |alpha| {
+            let critical_value = regression
+                .clone()
+                .and_then(|regression| get_critical_value(regression, self.size, alpha));
+            (alpha, critical_value)
+        })
+    }
+
+    /// Returns whether the null hypothesis of a unit root is rejected at the given
+    /// alpha level, i.e. whether the test statistic is below the Dickey-Fuller critical
+    /// value for the regression and size of this report.
+    ///
+    /// This is the left-tail decision of the (Augmented) Dickey-Fuller test: `true` means
+    /// the series is deemed stationary, `false` means we fail to reject the unit root.
+    ///
+    /// Returns `Error::InvalidParameter` if this is not a Dickey-Fuller report.
+    pub fn is_stationary(&self, alpha: AlphaLevel) -> Result<bool, Error> {
+        let critical_value: F =
+            get_critical_value(self.dickeyfuller_regression()?, self.size, alpha)?;
+        Ok(self.test_statistic < critical_value)
+    }
+
+    /// Returns the decision of the Dickey-Fuller test for the regression and size of
+    /// this report at each alpha level, from the most to the least stringent - see
+    /// [`Report::is_stationary`].
+    ///
+    /// E.g. a row of a summary table.
+    pub fn decisions(&self) -> Result<[(AlphaLevel, TestDecision); 4], Error> {
+        let [a, b, c, d] = AlphaLevel::all().map(|alpha| {
+            let decision = if self.is_stationary(alpha)? {
+                TestDecision::Reject
+            } else {
+                TestDecision::FailToReject
+            };
+            Ok((alpha, decision))
+        });
+
+        Ok([a?, b?, c?, d?])
+    }
+
+    /// Returns the most stringent alpha level at which the unit root is rejected, or
+    /// `None` if it is not rejected at any tabulated level - see [`Report::decisions`].
+    ///
+    /// E.g. `Some(AlphaLevel::FivePercent)` brackets the p-value between 2.5% and 5%
+    /// without computing it.
+    pub fn effective_alpha(&self) -> Result<Option<AlphaLevel>, Error> {
+        Ok(self
+            .decisions()?
+            .into_iter()
+            .find(|(_, decision)| *decision == TestDecision::Reject)
+            .map(|(alpha, _)| alpha))
+    }
+
+    /// Returns the signed margin `critical_value - test_statistic` of the Dickey-Fuller
+    /// test for the regression and size of this report.
+    ///
+    /// The margin is positive when the unit root is rejected and grows with the strength
+    /// of the evidence for stationarity, e.g. to rank series.
+    pub fn margin(&self, alpha: AlphaLevel) -> Result<F, Error> {
+        let critical_value: F =
+            get_critical_value(self.dickeyfuller_regression()?, self.size, alpha)?;
+        Ok(critical_value - self.test_statistic)
+    }
+
+    /// Returns the "Number of Observations Used" statsmodels' adfuller reports for the
+    /// same test, e.g. to cross-check a report against Python.
+    ///
+    /// statsmodels reports the rows of the final test regression, len(y) - usedlag - 1,
+    /// which is [`Report::size`]. This holds with `autolag` too: the lag is selected on
+    /// the common sample of the largest lag, len(y) - maxlag - 1 rows, but the test is
+    /// refitted on all the rows available at the selected lag - as
+    /// `adf_test_autolag`. Only the lag selection sample differs from the reported one.
+    pub fn nobs_statsmodels(&self) -> usize {
+        self.size
+    }
+
+    /// Returns whether the test regression kept at least min_df residual degrees of
+    /// freedom, e.g. to filter out fits of short series with many lags whose
+    /// t-statistics are unreliable.
+    ///
+    /// Reports of tests that are not regression-based, i.e. without `df_resid`, are
+    /// always adequate.
+    pub fn has_adequate_df(&self, min_df: usize) -> bool {
+        self.df_resid.is_none_or(|df| df >= min_df)
+    }
+}
+
+/// A configured unit root test.
+///
+/// Allows running a suite of tests through a uniform interface, e.g. a
+/// `Vec<Box<dyn UnitRootTest<f64>>>`.
+pub trait UnitRootTest<F: Debug + Clone> {
+    /// Runs the test on the series y.
+    fn run(&self, y: &DVector<F>) -> Result<Report<F>, Error>;
+
+    /// Returns the minimum length of the series for the test to run, e.g. to filter out
+    /// short series before a batch instead of collecting `Error::NotEnoughSamples`.
+    fn min_length(&self) -> usize;
+}
+
+/// Decision of a test at a given alpha level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestDecision {
+    /// The null hypothesis is rejected.
+    Reject,
+    /// The null hypothesis is not rejected.
+    FailToReject,
+}
+
+/// Joint conclusion of a unit root test (null: unit root) and a stationarity test
+/// (null: stationary).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conclusion {
+    /// The unit root is rejected and stationarity is not: the series is stationary.
+    Stationary,
+    /// Stationarity is rejected and the unit root is not: the series is non-stationary.
+    NonStationary,
+    /// Neither null is rejected: the data are not informative enough.
+    Inconclusive,
+    /// Both nulls are rejected: e.g. the series may be trend-stationary or have a
+    /// structural break.
+    Contradictory,
+}
+
+/// Combines an (Augmented) Dickey-Fuller report and a KPSS report following the
+/// usual confirmatory decision table:
+///
+/// |                   | KPSS not rejected | KPSS rejected    |
+/// |-------------------|-------------------|------------------|
+/// | ADF rejected      | `Stationary`      | `Contradictory`  |
+/// | ADF not rejected  | `Inconclusive`    | `NonStationary`  |
+///
+/// Returns `Error::InvalidParameter` unless adf is a Dickey-Fuller report and kpss a
+/// KPSS report, both carrying their regression - e.g. if they are swapped.
+pub fn conclude<F: Debug + Clone + Float>(
+    adf: &Report<F>,
+    kpss: &Report<F>,
+    alpha: AlphaLevel,
+) -> Result<Conclusion, Error> {
+    if kpss.kind != TestKind::Kpss {
+        return Err(Error::InvalidParameter(format!(
+            "expected a KPSS report, got a {:?} report",
+            kpss.kind
+        )));
+    }
+    let kpss_regression = kpss
+        .regression
+        .ok_or_else(|| Error::InvalidParameter("KPSS report does not carry a regression".into()))?;
+
+    // ADF is left-tailed, KPSS is right-tailed
+    let unit_root_rejected = adf.is_stationary(alpha)?;
+    let kpss_critical_value: F = kpss_critical_value(kpss_regression, alpha)?;
+    let stationarity_rejected = kpss.test_statistic > kpss_critical_value;
+
+    Ok(match (unit_root_rejected, stationarity_rejected) {
+        (true, false) => Conclusion::Stationary,
+        (false, true) => Conclusion::NonStationary,
+        (false, false) => Conclusion::Inconclusive,
+        (true, true) => Conclusion::Contradictory,
+    })
+}
+
+/// Augmented Dickey-Fuller and KPSS tests of a series around a linear trend - see
+/// [`trend_stationarity_check`].
+#[derive(Debug, Clone)]
+pub struct TrendStationarityReport<F: Debug + Clone> {
+    /// The Augmented Dickey-Fuller test with a constant and trend (null: unit root)
+    pub adf: Report<F>,
+    /// The KPSS test with a constant and trend (null: trend stationary)
+    pub kpss: Report<F>,
+    /// The joint conclusion of the two tests - see [`conclude`]
+    pub conclusion: Conclusion,
+}
+
+/// Distinguishes a deterministic trend from a stochastic one by running the Augmented
+/// Dickey-Fuller test with lag augmenting lags and the KPSS test, both with a constant
+/// and trend, and combining them with [`conclude`]:
+/// - `Conclusion::Stationary`: the series is trend-stationary
+/// - `Conclusion::NonStationary`: the series is difference-stationary
+///
+/// The KPSS long-run variance uses the Newey-West bandwidth for the length of y.
+pub fn trend_stationarity_check<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    alpha: AlphaLevel,
+) -> Result<TrendStationarityReport<F>, Error> {
+    let adf = adf::adf_test(y, lag, Regression::ConstantAndTrend)?;
+    let kpss = kpss::Kpss::new(Regression::ConstantAndTrend).run(y)?;
+    let conclusion = conclude(&adf, &kpss, alpha)?;
+
+    Ok(TrendStationarityReport {
+        adf,
+        kpss,
+        conclusion,
+    })
+}
+
+/// Transformation applied to a series before testing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// The series is used as-is
+    None,
+    /// The natural log of the series, e.g. log-prices
+    Log,
+    /// The first difference of the natural log of the series, e.g. log-returns
+    LogDiff,
+}
+
+impl Transform {
+    /// Applies the transformation to y.
+    /// Returns an error if a log is taken of a non-positive value.
+    pub fn apply<F: RealField + Scalar + Float>(
+        &self,
+        y: &DVector<F>,
+    ) -> Result<DVector<F>, Error> {
+        if *self == Transform::None {
+            return Ok(y.clone());
+        }
+
+        if let Some(i) = y.iter().position(|v| *v <= F::zero()) {
+            return Err(Error::NonPositiveValue(i));
+        }
+
+        let log_y = y.map(|v| Float::ln(v));
+
+        match self {
+            Transform::LogDiff => diff(&log_y),
+            _ => Ok(log_y),
+        }
+    }
+}
+
+/// Origin of the time trend columns of the test regression
+///
+/// With a constant in the regression, the unit root statistic is invariant to the
+/// choice: shifting the trend spans the same columns, so it changes the values of the
+/// constant - and of the linear trend with a quadratic trend - but neither the fit nor
+/// the coefficient of the level. Without a constant the shift is not absorbed and the
+/// statistic changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrendSpec {
+    /// t = 1, 2, ..., n, as statsmodels
+    #[default]
+    OneBased,
+    /// t = 0, 1, ..., n - 1: the constant is the intercept of the first row
+    ZeroBased,
+    /// t = i - (n + 1) / 2 for i = 1..n: the constant is the intercept at the middle of
+    /// the sample
+    Centered,
+}
+
+impl TrendSpec {
+    /// Returns the value of the trend at row i (0-based) of a regression of n rows.
+    fn value(&self, i: usize, n: usize) -> f64 {
+        match self {
+            TrendSpec::OneBased => (i + 1) as f64,
+            TrendSpec::ZeroBased => i as f64,
+            TrendSpec::Centered => (i + 1) as f64 - (n + 1) as f64 / 2.0,
+        }
+    }
 }
 
 /// Returns Delta(y) = y - y.shift(1) and a matrix made of:
 /// - a column of y.shift(1)
 /// - n columns of Delta(y).shift(n)
+/// - the deterministic columns - see [`add_deterministic_terms`]
 pub(crate) fn prepare<F: RealField + Scalar + Float>(
     y: &DVector<F>,
     n: usize,
-    regression: Regression,
+    deterministics: impl Into<Deterministics>,
+) -> Result<(DVector<F>, DMatrix<F>, usize), Error> {
+    prepare_with_trend(y, n, deterministics, TrendSpec::default())
+}
+
+/// Same as [`prepare`] with the origin of the trend columns given by trend.
+pub(crate) fn prepare_with_trend<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    n: usize,
+    deterministics: impl Into<Deterministics>,
+    trend: TrendSpec,
 ) -> Result<(DVector<F>, DMatrix<F>, usize), Error> {
     let y_len = y.len();
 
@@ -45,67 +409,281 @@ pub(crate) fn prepare<F: RealField + Scalar + Float>(
         return Err(Error::NotEnoughSamples);
     }
 
-    // remove last element to build y[t-1]
-    // it's length is y_len - 1
-    let y_t_1_full = y.clone().remove_row(y_len - 1);
+    // number of rows of the regression
+    let size = y_len - n - 1;
 
-    // build Delta[y[t]] = y[t] - y[t-1] by removing the first element of y
-    // and subtracting y[t-1].
-    // The length of Delta[y[t]] is y_len - 1.
-    let delta_y = y.clone().remove_row(0) - &y_t_1_full;
+    // Delta[y[t]] = y[t] - y[t-1], of length y_len - 1.
+    // Every column below is a slice of y or of this single buffer.
+    let delta_y = diff(y)?;
 
-    // we want to return as first element the last y_len - 1 -n elements of Delta[y[t]].
-    // we have to remove the first n elements of delta_y.
-    let delta_y_output = delta_y.clone().remove_rows(0, n);
+    // the target: the last y_len - 1 - n elements of Delta[y[t]]
+    let delta_y_output = delta_y.rows(n, size);
 
-    // Now for the second element of the tuple, we want to build a matrix of size (y_len
-    // - 1 - n) x (n + 1)
+    // the matrix of size (y_len - 1 - n) x (n + 1)
+    let mut x = DMatrix::zeros(size, n + 1);
 
-    // Create the empty matrix
-    let mut x = DMatrix::zeros(y_len - n - 1, n + 1);
+    // - The first column is y[t-1]
+    x.column_mut(LEVEL_COL).copy_from(&y.rows(n, size));
 
-    // - The first column is a column of y[t-1]
-    let y_t_1 = y_t_1_full.remove_rows(0, n);
-    x.column_mut(0).copy_from(&y_t_1);
+    // - The next n columns are Delta[y[t-i]] for i = 1..n
+    for i in 1..=n {
+        x.column_mut(i).copy_from(&delta_y.rows(n - i, size));
+    }
 
-    // - The next n columns are shifted elements of Delta[y[t]] (by removing the last element)
-    if n > 0 {
-        let mut delta_y_shifted = delta_y.clone().remove_row(delta_y.len() - 1);
+    add_deterministic_terms_with_trend(&mut x, deterministics, trend)?;
 
-        for i in 0..n {
-            let col = delta_y_shifted.clone().remove_rows(0, n - i - 1);
-            x.column_mut(i + 1).copy_from(&col);
-            let delta_y_shifted_len = delta_y_shifted.len();
-            delta_y_shifted = delta_y_shifted.remove_row(delta_y_shifted_len - 1);
-        }
+    Ok((delta_y_output.into_owned(), x, size))
+}
+
+/// Returns the target of the (Augmented) Dickey-Fuller regression with lag augmenting
+/// lags: Delta(y) without its first lag elements, aligned with the rows of the design
+/// matrix - see [`prepare`].
+///
+/// The target does not depend on the regression, which only adds columns to the design
+/// matrix.
+pub fn differenced_target<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<DVector<F>, Error> {
+    let (delta_y, _x, _size) = prepare(y, lag, regression)?;
+    Ok(delta_y)
+}
+
+/// The target and design matrix of the (Augmented) Dickey-Fuller regression, with the
+/// blocks of columns named - see [`prepare_named`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedDesign<F: RealField + Scalar + Float> {
+    target: DVector<F>,
+    design: DMatrix<F>,
+    lag: usize,
+    regression: Regression,
+}
+
+impl<F: RealField + Scalar + Float> PreparedDesign<F> {
+    /// Returns the target Delta(y[t]).
+    pub fn target(&self) -> &DVector<F> {
+        &self.target
+    }
+
+    /// Returns the whole design matrix, its columns in the order of
+    /// [`coefficient_names`].
+    pub fn design(&self) -> &DMatrix<F> {
+        &self.design
     }
 
+    /// Returns the level column y[t-1], whose t-statistic is the test statistic.
+    pub fn level(&self) -> DVectorView<'_, F> {
+        self.design.column(LEVEL_COL)
+    }
+
+    /// Returns the lag columns Delta(y[t-1]), ..., Delta(y[t-lag]), in that order - no
+    /// columns without augmenting lags.
+    pub fn diff_lags(&self) -> DMatrixView<'_, F> {
+        self.design.columns(LEVEL_COL + 1, self.lag)
+    }
+
+    /// Returns the constant column, if the regression has one.
+    pub fn constant(&self) -> Option<DVectorView<'_, F>> {
+        (self.regression != Regression::NoConstantNoTrend).then(|| self.design.column(self.lag + 1))
+    }
+
+    /// Returns the time trend column 1, 2, ..., n, if the regression has one.
+    pub fn trend(&self) -> Option<DVectorView<'_, F>> {
+        (self.regression == Regression::ConstantAndTrend).then(|| self.design.column(self.lag + 2))
+    }
+}
+
+/// Returns the target and design matrix of the (Augmented) Dickey-Fuller regression of y
+/// with lag augmenting lags, with typed accessors to each block of columns, e.g. to
+/// inspect the regression the test statistic comes from.
+///
+/// The design is the one [`adf_test`](crate::prelude::tools::adf_test) fits.
+pub fn prepare_named<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+) -> Result<PreparedDesign<F>, Error> {
+    let (target, design, _size) = prepare(y, lag, regression)?;
+
+    Ok(PreparedDesign {
+        target,
+        design,
+        lag,
+        regression,
+    })
+}
+
+/// Returns the names of the coefficients of the (Augmented) Dickey-Fuller regression
+/// with lag augmenting lags, in the order of the columns of the design matrix built by
+/// [`prepare`]: "level", "d_lag_1", ..., "d_lag_{lag}", then "const" and "trend" as
+/// implied by the regression.
+pub fn coefficient_names(lag: usize, regression: Regression) -> Vec<String> {
+    let mut names = vec!["level".to_string()];
+    names.extend((1..=lag).map(|i| format!("d_lag_{}", i)));
+
     if regression != Regression::NoConstantNoTrend {
+        names.push("const".to_string());
+    }
+    if regression == Regression::ConstantAndTrend {
+        names.push("trend".to_string());
+    }
+
+    names
+}
+
+/// Same as [`prepare`] with additional user-supplied deterministic columns appended
+/// after the built-in constant and trend.
+///
+/// Each column must have the same length as y; its first n + 1 elements are dropped to
+/// align it with Delta(y).
+pub(crate) fn prepare_with_deterministics<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    n: usize,
+    regression: Regression,
+    deterministics: &[DVector<F>],
+) -> Result<(DVector<F>, DMatrix<F>, usize), Error> {
+    if let Some(i) = deterministics.iter().position(|d| d.len() != y.len()) {
+        return Err(Error::InvalidParameter(format!(
+            "deterministic column {} has length {} but the series has length {}",
+            i,
+            deterministics[i].len(),
+            y.len()
+        )));
+    }
+
+    let (delta_y, mut x, size) = prepare(y, n, regression)?;
+
+    for d in deterministics {
+        x.extend(d.rows(n + 1, size).iter().cloned());
+    }
+
+    Ok((delta_y, x, size))
+}
+
+/// Returns the first difference y[t] - y[t-1] of y, one element shorter than y.
+/// Returns `Error::NotEnoughSamples` if y has fewer than 2 elements.
+pub(crate) fn diff<F: RealField + Scalar + Float>(y: &DVector<F>) -> Result<DVector<F>, Error> {
+    if y.len() < 2 {
+        return Err(Error::NotEnoughSamples);
+    }
+    Ok(lagged_diff(y, 1))
+}
+
+/// Returns y[t] - y[t-period] for t = period..len(y), or an empty vector if y is not
+/// longer than period.
+///
+/// The subtraction runs over two contiguous slices of the storage of y so that it is
+/// auto-vectorized.
+pub(crate) fn lagged_diff<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    period: usize,
+) -> DVector<F> {
+    let y = y.as_slice();
+    if y.len() <= period {
+        return DVector::zeros(0);
+    }
+
+    let (lagged, current) = (&y[..y.len() - period], &y[period..]);
+    let delta = current
+        .iter()
+        .zip(lagged)
+        .map(|(a, b)| *a - *b)
+        .collect::<Vec<F>>();
+
+    DVector::from_vec(delta)
+}
+
+/// Returns the elements of x in increasing order.
+pub(crate) fn sorted<F: Float>(x: &[F]) -> Vec<F> {
+    let mut sorted = x.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted
+}
+
+/// Returns the q quantile of the already sorted values, for q in [0, 1], linearly
+/// interpolated between order statistics - see `utils::quantile`. Returns NaN if sorted
+/// is empty.
+pub(crate) fn sorted_quantile<F: Float>(sorted: &[F], q: F) -> F {
+    if sorted.is_empty() {
+        return F::nan();
+    }
+
+    let last = sorted.len() - 1;
+    let pos = q * F::from(last).unwrap_or_else(F::nan);
+    let lo = Float::floor(pos).to_usize().unwrap_or(0).min(last);
+    let hi = Float::ceil(pos).to_usize().unwrap_or(last).min(last);
+    let frac = pos - Float::floor(pos);
+    sorted[lo] + frac * (sorted[hi] - sorted[lo])
+}
+
+/// Returns the number of deterministic columns implied by the regression.
+pub(crate) fn deterministic_terms(deterministics: impl Into<Deterministics>) -> usize {
+    deterministics.into().bits().count_ones() as usize
+}
+
+/// Returns the minimum length of y for the (Augmented) Dickey-Fuller regression with lag
+/// augmenting lags: the lag + 1 observations consumed by the differences must leave
+/// more rows than the lag + 1 + deterministic columns of the design matrix.
+pub(crate) fn adf_min_length(lag: usize, regression: Regression) -> usize {
+    2 * lag + 3 + deterministic_terms(regression)
+}
+
+/// Appends the deterministic columns implied by the regression or deterministics to x:
+/// - a column of 1s for the constant
+/// - a column 1, 2, ..., n for the time trend
+/// - a column 1, 4, ..., n^2 for the quadratic time trend
+///
+/// See [`add_deterministic_terms_with_trend`] for another origin of the trend.
+pub(crate) fn add_deterministic_terms<F: RealField + Scalar + Float>(
+    x: &mut DMatrix<F>,
+    deterministics: impl Into<Deterministics>,
+) -> Result<(), Error> {
+    add_deterministic_terms_with_trend(x, deterministics, TrendSpec::default())
+}
+
+/// Same as [`add_deterministic_terms`] with the origin of the trend columns given by
+/// trend.
+pub(crate) fn add_deterministic_terms_with_trend<F: RealField + Scalar + Float>(
+    x: &mut DMatrix<F>,
+    deterministics: impl Into<Deterministics>,
+    trend: TrendSpec,
+) -> Result<(), Error> {
+    let deterministics = deterministics.into();
+    let nrows = x.nrows();
+
+    if deterministics.contains(Deterministics::CONST) {
         // constant trend column
         let constant = F::from(1.0).ok_or(Error::ConversionFailed)?;
         let a = vec![constant; x.nrows()];
         x.extend(a)
     }
 
-    if regression == Regression::ConstantAndTrend {
-        // time trend column
-        let tt: Result<Vec<F>, crate::Error> = (1..x.nrows() + 1)
-            .map(|i| F::from(i as f64).ok_or(Error::ConversionFailed))
-            .collect();
-        match tt {
-            Ok(tt) => x.extend(tt),
-            Err(_) => return Err(Error::ConversionFailed),
-        };
+    // time trend columns: t then t^2
+    for (flag, power) in [(Deterministics::TREND, 1), (Deterministics::TREND2, 2)] {
+        if deterministics.contains(flag) {
+            let tt: Result<Vec<F>, crate::Error> = (0..nrows)
+                .map(|i| F::from(trend.value(i, nrows).powi(power)).ok_or(Error::ConversionFailed))
+                .collect();
+            match tt {
+                Ok(tt) => x.extend(tt),
+                Err(_) => return Err(Error::ConversionFailed),
+            };
+        }
     }
 
-    Ok((delta_y_output.into_owned(), x, y_len - n - 1))
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use nalgebra::{DMatrix, Matrix, Vector};
+    use nalgebra::{DMatrix, DVector, Matrix, Vector};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
 
-    use crate::distrib::Regression;
+    use super::TestDecision;
+    use crate::distrib::{AlphaLevel, Regression};
+    use crate::utils::gen_ar_1;
 
     #[test]
     fn test_prepare_constant() {
@@ -317,4 +895,370 @@ mod tests {
         let res = super::prepare(&y, n, Regression::Constant);
         assert!(res.is_err());
     }
+
+    #[test]
+    fn test_transform_log_diff() {
+        let y = Matrix::from(vec![1.0f64, 2.0, 4.0, 2.0]);
+
+        let log_y = super::Transform::Log.apply(&y).unwrap();
+        assert_eq!(log_y, y.map(|v| v.ln()));
+
+        let returns = super::Transform::LogDiff.apply(&y).unwrap();
+        let ln_2 = 2.0f64.ln();
+        assert_eq!(returns, Vector::from(vec![ln_2, ln_2, -ln_2]));
+
+        assert_eq!(super::Transform::None.apply(&y).unwrap(), y);
+    }
+
+    #[test]
+    fn test_transform_non_positive() {
+        let y = Matrix::from(vec![1.0f64, 2.0, 0.0, 2.0]);
+
+        let res = super::Transform::Log.apply(&y);
+        assert!(matches!(res, Err(crate::Error::NonPositiveValue(2))));
+
+        let res = super::Transform::LogDiff.apply(&y);
+        assert!(matches!(res, Err(crate::Error::NonPositiveValue(2))));
+
+        assert!(super::Transform::None.apply(&y).is_ok());
+    }
+
+    /// Dickey-Fuller report of a regression with a constant on 25 observations, whose
+    /// critical values are 1%: -3.724, 2.5%: -3.318, 5%: -2.986, 10%: -2.633.
+    fn df_report(test_statistic: f64) -> super::Report<f64> {
+        super::Report {
+            test_statistic,
+            size: 25,
+            dropped_leading: 3,
+            kind: super::TestKind::DickeyFuller,
+            regression: Some(Regression::Constant),
+            df_resid: Some(23),
+        }
+    }
+
+    #[test]
+    fn test_report_critical_values() {
+        let report = df_report(-3.0);
+
+        let critical_values = report.critical_values().unwrap();
+        assert_eq!(critical_values.len(), 4);
+        assert_eq!(critical_values[0].0, AlphaLevel::OnePercent);
+        assert!((critical_values[0].1 - -3.724).abs() < 1e-3);
+        assert_eq!(critical_values[3].0, AlphaLevel::TenPercent);
+        assert!((critical_values[3].1 - -2.633).abs() < 1e-3);
+
+        let report = super::Report {
+            regression: None,
+            ..report
+        };
+        assert!(report.critical_values().is_err());
+
+        // the Dickey-Fuller tables do not apply to other statistics
+        let report = super::Report {
+            kind: super::TestKind::Other,
+            regression: Some(Regression::Constant),
+            ..report
+        };
+        assert!(matches!(
+            report.critical_values(),
+            Err(crate::Error::InvalidParameter(_))
+        ));
+        assert!(report.critical_values_iter().all(|(_, cv)| cv.is_err()));
+        assert!(report.is_stationary(AlphaLevel::FivePercent).is_err());
+        assert!(report.decisions().is_err());
+        assert!(report.effective_alpha().is_err());
+        assert!(report.margin(AlphaLevel::FivePercent).is_err());
+    }
+
+    #[test]
+    fn test_report_critical_values_iter() {
+        let report = df_report(-3.0);
+
+        let critical_values: Vec<_> = report
+            .critical_values_iter()
+            .map(|(alpha, cv)| (alpha, cv.unwrap()))
+            .collect();
+        assert_eq!(critical_values, report.critical_values().unwrap());
+
+        let report = super::Report { size: 5, ..report };
+        assert!(report.critical_values_iter().all(|(_, cv)| cv.is_err()));
+    }
+
+    #[test]
+    fn test_report_decision_table() {
+        // (test statistic, stationary at 1%, 2.5%, 5%, 10%, effective alpha)
+        let table = [
+            (
+                -10.0,
+                [true, true, true, true],
+                Some(AlphaLevel::OnePercent),
+            ),
+            (
+                -3.5,
+                [false, true, true, true],
+                Some(AlphaLevel::TwoPointFivePercent),
+            ),
+            (
+                -3.0,
+                [false, false, true, true],
+                Some(AlphaLevel::FivePercent),
+            ),
+            (
+                -2.7,
+                [false, false, false, true],
+                Some(AlphaLevel::TenPercent),
+            ),
+            (0.0, [false, false, false, false], None),
+        ];
+
+        let critical_values = [-3.724, -3.318, -2.986, -2.633];
+
+        for (test_statistic, stationary, effective_alpha) in table {
+            let report = df_report(test_statistic);
+
+            for ((alpha, stationary), critical_value) in AlphaLevel::all()
+                .into_iter()
+                .zip(stationary)
+                .zip(critical_values)
+            {
+                assert_eq!(
+                    report.is_stationary(alpha).unwrap(),
+                    stationary,
+                    "{test_statistic} at {alpha:?}"
+                );
+
+                let margin = report.margin(alpha).unwrap();
+                assert!(
+                    (margin - (critical_value - test_statistic)).abs() < 1e-3,
+                    "{test_statistic} at {alpha:?}"
+                );
+                assert_eq!(margin > 0.0, stationary, "{test_statistic} at {alpha:?}");
+            }
+            assert_eq!(
+                report.effective_alpha().unwrap(),
+                effective_alpha,
+                "{test_statistic}"
+            );
+
+            let decisions = report.decisions().unwrap();
+            for ((alpha, decision), (expected_alpha, stationary)) in decisions
+                .into_iter()
+                .zip(AlphaLevel::all().into_iter().zip(stationary))
+            {
+                let expected = if stationary {
+                    TestDecision::Reject
+                } else {
+                    TestDecision::FailToReject
+                };
+                assert_eq!(alpha, expected_alpha);
+                assert_eq!(decision, expected, "{test_statistic} at {alpha:?}");
+            }
+        }
+
+        let report = super::Report {
+            size: 0,
+            ..df_report(-3.0)
+        };
+        assert!(report.decisions().is_err());
+    }
+
+    #[test]
+    fn test_report_has_adequate_df() {
+        let y = Matrix::from(vec![1., 3., 2., 5., 4., 6., 8., 7., 9., 12.]);
+
+        let report = crate::tools::adf::adf_test(&y, 3, Regression::Constant).unwrap();
+        assert_eq!(report.df_resid, Some(1));
+        assert!(report.has_adequate_df(1));
+        assert!(!report.has_adequate_df(2));
+
+        let report = super::Report {
+            df_resid: None,
+            ..report
+        };
+        assert!(report.has_adequate_df(100));
+    }
+
+    #[test]
+    fn test_prepare_named() {
+        let y = DVector::from_row_slice(&[1., 3., 6., 10., 15., 21., 28., 36., 45., 55.]);
+
+        let design = super::prepare_named(&y, 2, Regression::ConstantAndTrend).unwrap();
+        let (delta_y, x, _sz) = super::prepare(&y, 2, Regression::ConstantAndTrend).unwrap();
+
+        assert_eq!(design.target(), &delta_y);
+        assert_eq!(design.design(), &x);
+        // y[t-1] and Delta(y[t-1]), Delta(y[t-2]) from t = 3
+        assert_eq!(design.level().rows(0, 2), y.rows(2, 2));
+        assert_eq!(design.diff_lags().ncols(), 2);
+        assert_eq!(design.diff_lags()[(0, 0)], 3.0);
+        assert_eq!(design.diff_lags()[(0, 1)], 2.0);
+        assert!(design.constant().unwrap().iter().all(|v| *v == 1.0));
+        assert_eq!(design.trend().unwrap()[6], 7.0);
+
+        let design = super::prepare_named(&y, 0, Regression::NoConstantNoTrend).unwrap();
+        assert_eq!(design.diff_lags().ncols(), 0);
+        assert!(design.constant().is_none());
+        assert!(design.trend().is_none());
+
+        let design = super::prepare_named(&y, 1, Regression::Constant).unwrap();
+        assert!(design.constant().is_some());
+        assert!(design.trend().is_none());
+    }
+
+    #[test]
+    fn test_coefficient_names() {
+        let y = Matrix::from(vec![1., 3., 6., 10., 15., 21., 28., 36., 45., 55.]);
+
+        for regression in [
+            Regression::NoConstantNoTrend,
+            Regression::Constant,
+            Regression::ConstantAndTrend,
+        ] {
+            for n in 0..3 {
+                let (_delta_y, x, _sz) = super::prepare(&y, n, regression).unwrap();
+                let names = super::coefficient_names(n, regression);
+                assert_eq!(names.len(), x.ncols());
+                assert_eq!(names[super::LEVEL_COL], "level");
+            }
+        }
+
+        assert_eq!(
+            super::coefficient_names(2, Regression::ConstantAndTrend),
+            vec!["level", "d_lag_1", "d_lag_2", "const", "trend"]
+        );
+    }
+
+    #[test]
+    fn test_differenced_target() {
+        let n = 2;
+
+        let y = Matrix::from(vec![1., 3., 6., 10., 15., 21., 28., 36., 45., 55.]);
+
+        let delta_y = super::differenced_target(&y, n, Regression::ConstantAndTrend).unwrap();
+        assert_eq!(delta_y, Vector::from(vec![4., 5., 6., 7., 8., 9., 10.]));
+        assert_eq!(
+            delta_y,
+            super::prepare(&y, n, Regression::Constant).unwrap().0
+        );
+
+        let y = Matrix::from(vec![1., 3.]);
+        assert!(super::differenced_target(&y, n, Regression::Constant).is_err());
+    }
+
+    #[test]
+    fn test_prepare_with_deterministics() {
+        let n = 2;
+
+        let y = Matrix::from(vec![1., 3., 6., 10., 15., 21., 28., 36., 45., 55.]);
+        let seasonal = Matrix::from(vec![1., 0., 1., 0., 1., 0., 1., 0., 1., 0.]);
+
+        let (delta_y, x, sz) =
+            super::prepare_with_deterministics(&y, n, Regression::Constant, &[seasonal]).unwrap();
+        let (expected_delta_y, expected_x, expected_sz) =
+            super::prepare(&y, n, Regression::Constant).unwrap();
+
+        assert_eq!(sz, expected_sz);
+        assert_eq!(delta_y, expected_delta_y);
+        assert_eq!(x.ncols(), expected_x.ncols() + 1);
+        assert_eq!(x.columns(0, expected_x.ncols()), expected_x);
+        assert_eq!(
+            x.column(expected_x.ncols()).into_owned(),
+            Vector::from(vec![0., 1., 0., 1., 0., 1., 0.])
+        );
+
+        let too_short = Matrix::from(vec![1., 0., 1.]);
+        let res = super::prepare_with_deterministics(&y, n, Regression::Constant, &[too_short]);
+        assert!(matches!(res, Err(crate::Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_prepare_level_column() {
+        let n = 2;
+
+        let y = Matrix::from(vec![1., 3., 6., 10., 15., 21., 28., 36., 45., 55.]);
+
+        for regression in [
+            Regression::NoConstantNoTrend,
+            Regression::Constant,
+            Regression::ConstantAndTrend,
+        ] {
+            let (_, x, _) = super::prepare(&y, n, regression).unwrap();
+
+            // y[t-1] aligned with Delta[y[t]]
+            assert_eq!(
+                x.column(super::LEVEL_COL).into_owned(),
+                Vector::from(vec![6., 10., 15., 21., 28., 36., 45.])
+            );
+        }
+    }
+
+    #[test]
+    fn test_conclude() {
+        // 5% critical values: ADF at n=25: -2.986, KPSS level: 0.463
+        let report = |kind, test_statistic| super::Report {
+            test_statistic,
+            size: 25,
+            dropped_leading: 3,
+            kind,
+            regression: Some(Regression::Constant),
+            df_resid: Some(23),
+        };
+        let adf_report = |test_statistic| report(super::TestKind::DickeyFuller, test_statistic);
+        let kpss_report = |test_statistic| report(super::TestKind::Kpss, test_statistic);
+
+        let test_data = [
+            (-4.0, 0.1, super::Conclusion::Stationary),
+            (-1.0, 0.9, super::Conclusion::NonStationary),
+            (-1.0, 0.1, super::Conclusion::Inconclusive),
+            (-4.0, 0.9, super::Conclusion::Contradictory),
+        ];
+        for (adf, kpss, expected) in test_data {
+            let conclusion = super::conclude(
+                &adf_report(adf),
+                &kpss_report(kpss),
+                AlphaLevel::FivePercent,
+            )
+            .unwrap();
+            assert_eq!(conclusion, expected);
+        }
+
+        let kpss = super::Report {
+            regression: None,
+            ..kpss_report(0.1)
+        };
+        assert!(super::conclude(&adf_report(-4.0), &kpss, AlphaLevel::FivePercent).is_err());
+
+        // the order of the reports is checked
+        assert!(matches!(
+            super::conclude(
+                &kpss_report(0.1),
+                &adf_report(-4.0),
+                AlphaLevel::FivePercent
+            ),
+            Err(crate::Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            super::conclude(&adf_report(-4.0), &adf_report(0.1), AlphaLevel::FivePercent),
+            Err(crate::Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_trend_stationarity_check() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let n = 200;
+
+        // deterministic trend plus stationary noise
+        let noise: DVector<f64> = gen_ar_1(&mut rng, n, 0.0, 0.3, 1.0);
+        let y = DVector::from_fn(n, |i, _| 0.5 * i as f64) + noise;
+        let report = super::trend_stationarity_check(&y, 1, AlphaLevel::FivePercent).unwrap();
+        assert_eq!(report.adf.regression, Some(Regression::ConstantAndTrend));
+        assert_eq!(report.kpss.regression, Some(Regression::ConstantAndTrend));
+        assert_eq!(report.conclusion, super::Conclusion::Stationary);
+
+        // random walk with drift
+        let y: DVector<f64> = gen_ar_1(&mut rng, n, 0.5, 1.0, 1.0);
+        let report = super::trend_stationarity_check(&y, 1, AlphaLevel::FivePercent).unwrap();
+        assert_eq!(report.conclusion, super::Conclusion::NonStationary);
+    }
 }
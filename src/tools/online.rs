@@ -0,0 +1,211 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streaming Augmented Dickey-Fuller test
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+use nalgebra::{DMatrix, DVector, RealField, Scalar};
+use num_traits::Float;
+
+use crate::distrib::Regression;
+use crate::prelude::tools::{Report, TestKind};
+use crate::tools::{adf_min_length, deterministic_terms, LEVEL_COL};
+use crate::Error;
+
+/// Augmented Dickey-Fuller test updated as new observations arrive, without recomputing
+/// the differences nor the regression over the whole series.
+///
+/// Each new y[t] adds exactly one row to the design matrix of [`adf_test`]: y[t-1], the
+/// lag differences Delta(y[t-1]), ..., Delta(y[t-lag]) and the deterministic terms,
+/// with target Delta(y[t]). The last lag + 1 raw values are kept to form that row in
+/// O(lag), and it is accumulated in the cross-products A'A and A'y in O(k^2) with k the
+/// number of regressors - independently of the length of the series.
+///
+/// # Warm-up
+/// The first lag + 1 observations only fill the buffer of raw values, and the regression
+/// needs more rows than regressors: [`OnlineAdf::report`] returns
+/// `Error::NotEnoughSamples` until [`OnlineAdf::min_length`] observations have been
+/// pushed.
+///
+/// [`adf_test`]: crate::prelude::tools::adf_test
+#[derive(Debug, Clone)]
+pub struct OnlineAdf<F: Float + Scalar + RealField> {
+    lag: usize,
+    regression: Regression,
+    /// The last lag + 1 raw values, oldest first
+    last: VecDeque<F>,
+    /// A'A
+    ata: DMatrix<F>,
+    /// A'y
+    aty: DVector<F>,
+    /// y'y
+    yty: F,
+    /// The number of rows of the regression
+    size: usize,
+}
+
+impl<F: Float + Scalar + RealField> OnlineAdf<F> {
+    /// Returns an empty test with lag augmenting lags and the deterministic terms of the
+    /// regression.
+    pub fn new(lag: usize, regression: Regression) -> Self {
+        let k = lag + 1 + deterministic_terms(regression);
+
+        Self {
+            lag,
+            regression,
+            last: VecDeque::with_capacity(lag + 1),
+            ata: DMatrix::zeros(k, k),
+            aty: DVector::zeros(k),
+            yty: F::zero(),
+            size: 0,
+        }
+    }
+
+    /// Returns the number of observations to push before the test can be computed - see
+    /// `Adf::min_length`.
+    pub fn min_length(&self) -> usize {
+        adf_min_length(self.lag, self.regression)
+    }
+
+    /// Adds the observation y[t] to the test.
+    pub fn push(&mut self, y: F) -> Result<(), Error> {
+        if self.last.len() == self.lag + 1 {
+            let row = self.design_row()?;
+            let delta_y = y - self.last[self.lag];
+
+            self.ata.ger(F::one(), &row, &row, F::one());
+            self.aty.axpy(delta_y, &row, F::one());
+            self.yty += delta_y * delta_y;
+            self.size += 1;
+
+            self.last.pop_front();
+        }
+        self.last.push_back(y);
+
+        Ok(())
+    }
+
+    /// Returns the row of the design matrix of the next observation.
+    fn design_row(&self) -> Result<DVector<F>, Error> {
+        let mut row = DVector::zeros(self.ata.nrows());
+
+        // y[t-1], then Delta(y[t-i]) for i = 1..lag
+        row[LEVEL_COL] = self.last[self.lag];
+        for i in 1..=self.lag {
+            row[i] = self.last[self.lag + 1 - i] - self.last[self.lag - i];
+        }
+
+        let mut col = self.lag + 1;
+        if self.regression != Regression::NoConstantNoTrend {
+            row[col] = F::one();
+            col += 1;
+        }
+        if self.regression == Regression::ConstantAndTrend {
+            // the trend is the index of the row, starting at 1
+            row[col] = F::from(self.size + 1).ok_or(Error::ConversionFailed)?;
+        }
+
+        Ok(row)
+    }
+
+    /// Returns the Augmented Dickey-Fuller report on the observations pushed so far - the
+    /// same as [`adf_test`] on the whole series.
+    ///
+    /// [`adf_test`]: crate::prelude::tools::adf_test
+    pub fn report(&self) -> Result<Report<F>, Error> {
+        let k = self.ata.nrows();
+        if self.size <= k {
+            return Err(Error::NotEnoughSamples);
+        }
+
+        let ata_inv = self
+            .ata
+            .clone()
+            .try_inverse()
+            .ok_or_else(|| Error::FailedToInvertMatrix("OLS failed to invert A.T*A".into()))?;
+        let betas = &ata_inv * &self.aty;
+
+        // y'y - 2 b'A'y + b'A'A b with A'A b = A'y
+        let ssr = Float::max(self.yty - betas.dot(&self.aty), F::zero());
+        let df_resid = self.size - k;
+        let sigma2 = ssr / F::from(df_resid).ok_or(Error::ConversionFailed)?;
+        let std_err = Float::sqrt(sigma2 * ata_inv[(LEVEL_COL, LEVEL_COL)]);
+
+        Ok(Report {
+            test_statistic: betas[LEVEL_COL] / std_err,
+            size: self.size,
+            dropped_leading: self.lag + 1,
+            kind: TestKind::DickeyFuller,
+            regression: Some(self.regression),
+            df_resid: Some(df_resid),
+        })
+    }
+
+    /// Returns the test statistic on the observations pushed so far - see
+    /// [`OnlineAdf::report`].
+    pub fn statistic(&self) -> Result<F, Error> {
+        Ok(self.report()?.test_statistic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use nalgebra::DVector;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::OnlineAdf;
+    use crate::distrib::Regression;
+    use crate::tools::adf::adf_test;
+    use crate::utils::gen_ar_1;
+    use crate::Error;
+
+    #[test]
+    fn test_online_adf() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y: DVector<f64> = gen_ar_1(&mut rng, 100, 0.5, 0.8, 1.0);
+
+        for regression in [
+            Regression::NoConstantNoTrend,
+            Regression::Constant,
+            Regression::ConstantAndTrend,
+        ] {
+            for lag in [0, 2] {
+                let mut online = OnlineAdf::new(lag, regression);
+
+                for (t, v) in y.iter().enumerate() {
+                    online.push(*v).unwrap();
+
+                    let n = t + 1;
+                    if n < online.min_length() {
+                        assert!(matches!(online.statistic(), Err(Error::NotEnoughSamples)));
+                        continue;
+                    }
+
+                    let expected = adf_test(&y.rows(0, n).into_owned(), lag, regression).unwrap();
+                    let report = online.report().unwrap();
+                    assert_relative_eq!(
+                        report.test_statistic,
+                        expected.test_statistic,
+                        epsilon = 1e-6
+                    );
+                    assert_eq!(report.size, expected.size);
+                    assert_eq!(report.df_resid, expected.df_resid);
+                }
+            }
+        }
+    }
+}
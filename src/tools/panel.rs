@@ -0,0 +1,146 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Panel unit root tests: combination of per-series p-values.
+use num_traits::Float;
+
+use crate::distrib::{chi_square_survival, normal_cdf, normal_quantile};
+use crate::Error;
+
+/// Method used to combine independent p-values into a single one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombineMethod {
+    /// Fisher's method: $-2 \sum_i \ln p_i$ follows a chi-square distribution with 2k
+    /// degrees of freedom under the joint null.
+    Fisher,
+    /// Stouffer's method: $\sum_i \Phi^{-1}(1 - p_i) / \sqrt{k}$ follows a standard
+    /// normal distribution under the joint null.
+    Stouffer,
+}
+
+/// Returns the combined p-value of k independent tests of the same null hypothesis,
+/// e.g. the per-series MacKinnon p-values of ADF tests across a panel - the Maddala-Wu
+/// panel unit root test with `CombineMethod::Fisher`.
+///
+/// The joint null is that every series has a unit root; a small combined p-value means
+/// at least some of the series are stationary.
+///
+/// Returns `Error::NotEnoughSamples` if pvals is empty and `Error::InvalidParameter` if
+/// a p-value is not in (0, 1].
+///
+/// # Examples:
+///
+/// ```rust
+/// use unit_root::prelude::tools::{combine_p_values, CombineMethod};
+///
+/// let p_value = combine_p_values(&[0.01f64, 0.02, 0.3], CombineMethod::Fisher).unwrap();
+/// assert!((p_value - 0.003478302).abs() < 1e-9);
+/// ```
+///
+/// # References
+/// - Maddala, G.S. and Wu, S. (1999). A comparative study of unit root tests with panel
+///   data and a new simple test. Oxford Bulletin of Economics and Statistics 61.
+/// - [Scipy](https://docs.scipy.org/doc/scipy/reference/generated/scipy.stats.combine_pvalues.html)
+pub fn combine_p_values<F: Float>(pvals: &[F], method: CombineMethod) -> Result<F, Error> {
+    if pvals.is_empty() {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let pvals = pvals
+        .iter()
+        .map(|p| p.to_f64().ok_or(Error::ConversionFailed))
+        .collect::<Result<Vec<f64>, Error>>()?;
+
+    if let Some(p) = pvals.iter().find(|p| !(**p > 0.0 && **p <= 1.0)) {
+        return Err(Error::InvalidParameter(format!(
+            "p-values must be in (0, 1], got {}",
+            p
+        )));
+    }
+
+    let k = pvals.len();
+    let p = match method {
+        CombineMethod::Fisher => {
+            let statistic = -2.0 * pvals.iter().map(|p| p.ln()).sum::<f64>();
+            chi_square_survival(statistic, (2 * k) as f64)
+        }
+        CombineMethod::Stouffer => {
+            let z = pvals.iter().map(|p| normal_quantile(1.0 - p)).sum::<f64>() / (k as f64).sqrt();
+            1.0 - normal_cdf(z)
+        }
+    };
+
+    F::from(p).ok_or(Error::ConversionFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_combine_p_values() {
+        // scipy.stats.combine_pvalues
+        let test_data = [
+            (
+                vec![0.01, 0.02, 0.3],
+                0.003478302009247751,
+                0.0023156944769524568,
+            ),
+            (
+                vec![0.1, 0.2, 0.3, 0.4],
+                0.14834596229462954,
+                0.0734651140267808,
+            ),
+        ];
+
+        for (pvals, fisher, stouffer) in test_data {
+            assert_relative_eq!(
+                combine_p_values(&pvals, CombineMethod::Fisher).unwrap(),
+                fisher,
+                epsilon = 1e-12
+            );
+            assert_relative_eq!(
+                combine_p_values(&pvals, CombineMethod::Stouffer).unwrap(),
+                stouffer,
+                epsilon = 1e-7
+            );
+        }
+
+        // a single p-value is left unchanged
+        assert_relative_eq!(
+            combine_p_values(&[0.2f64], CombineMethod::Fisher).unwrap(),
+            0.2,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_combine_p_values_invalid() {
+        let empty: [f64; 0] = [];
+        assert!(matches!(
+            combine_p_values(&empty, CombineMethod::Fisher),
+            Err(Error::NotEnoughSamples)
+        ));
+        assert!(matches!(
+            combine_p_values(&[0.5, 0.0], CombineMethod::Stouffer),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            combine_p_values(&[0.5, 1.5], CombineMethod::Fisher),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time series with metadata
+use std::fmt::Debug;
+use std::ops::Deref;
+
+use nalgebra::{DVector, Scalar};
+use num_traits::Float;
+
+use crate::distrib::AlphaLevel;
+use crate::tools::export::ReportRow;
+use crate::tools::{Report, UnitRootTest};
+use crate::Error;
+
+/// A series of observations with an optional name and frequency.
+///
+/// Dereferences to the underlying `DVector`, so a `&TimeSeries<F>` can be passed to every
+/// test entry point taking a `&DVector<F>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeries<F: Scalar> {
+    values: DVector<F>,
+    name: Option<String>,
+    frequency: Option<usize>,
+}
+
+impl<F: Scalar> TimeSeries<F> {
+    /// Returns a series with no name nor frequency.
+    pub fn new(values: DVector<F>) -> Self {
+        Self {
+            values,
+            name: None,
+            frequency: None,
+        }
+    }
+
+    /// Sets the name of the series, e.g. to label its reports.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the number of observations per seasonal cycle, e.g. 4 for quarterly or 12
+    /// for monthly data - see `utils::seasonal_diff`.
+    pub fn with_frequency(mut self, frequency: usize) -> Self {
+        self.frequency = Some(frequency);
+        self
+    }
+
+    /// Returns the name of the series, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the number of observations per seasonal cycle, if any.
+    pub fn frequency(&self) -> Option<usize> {
+        self.frequency
+    }
+
+    /// Returns the observations, dropping the metadata.
+    pub fn into_inner(self) -> DVector<F> {
+        self.values
+    }
+}
+
+impl<F: Scalar + Debug + Clone + Float> TimeSeries<F> {
+    /// Runs the test on the series.
+    ///
+    /// If the series has a name, an error of the test is returned as `Error::Series`
+    /// with that name, e.g. to find the failing series of a batch.
+    pub fn run<T: UnitRootTest<F> + ?Sized>(&self, test: &T) -> Result<Report<F>, Error> {
+        test.run(&self.values).map_err(|source| match &self.name {
+            Some(name) => Error::Series {
+                name: name.clone(),
+                source: Box::new(source),
+            },
+            None => source,
+        })
+    }
+
+    /// Returns the row summarizing the Dickey-Fuller report of a test of this series -
+    /// see [`Report::to_row`] - with the name of the series as its identifier, or an
+    /// empty one if the series has no name.
+    pub fn to_row(
+        &self,
+        report: &Report<F>,
+        used_lag: usize,
+        alpha: AlphaLevel,
+    ) -> Result<ReportRow<F>, Error> {
+        report.to_row(self.name().unwrap_or_default(), used_lag, alpha)
+    }
+}
+
+impl<F: Scalar> Deref for TimeSeries<F> {
+    type Target = DVector<F>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<F: Scalar> AsRef<DVector<F>> for TimeSeries<F> {
+    fn as_ref(&self) -> &DVector<F> {
+        &self.values
+    }
+}
+
+impl<F: Scalar> From<DVector<F>> for TimeSeries<F> {
+    fn from(values: DVector<F>) -> Self {
+        Self::new(values)
+    }
+}
+
+impl<F: Scalar> From<Vec<F>> for TimeSeries<F> {
+    fn from(values: Vec<F>) -> Self {
+        Self::new(DVector::from_vec(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::DVector;
+
+    use super::TimeSeries;
+    use crate::distrib::{AlphaLevel, Regression};
+    use crate::tools::adf::{adf_test, Adf};
+    use crate::tools::kpss::kpss_test;
+    use crate::Error;
+
+    const Y: [f64; 11] = [
+        -1.06714348,
+        -1.14700339,
+        0.79204106,
+        -0.05845247,
+        -0.67476754,
+        -0.10396661,
+        1.82059282,
+        -0.51169443,
+        2.07712365,
+        1.85668086,
+        2.56363688,
+    ];
+
+    #[test]
+    fn test_time_series() {
+        let y = DVector::from_row_slice(&Y[..]);
+        let series = TimeSeries::from(Y.to_vec())
+            .with_name("gdp")
+            .with_frequency(4);
+
+        assert_eq!(series.name(), Some("gdp"));
+        assert_eq!(series.frequency(), Some(4));
+        assert_eq!(series.len(), 11);
+
+        let report = adf_test(&series, 1, Regression::Constant).unwrap();
+        let expected = adf_test(&y, 1, Regression::Constant).unwrap();
+        assert_eq!(report.test_statistic, expected.test_statistic);
+
+        let report = kpss_test(&series, Regression::Constant, 2).unwrap();
+        let expected = kpss_test(&y, Regression::Constant, 2).unwrap();
+        assert_eq!(report.test_statistic, expected.test_statistic);
+
+        let series = TimeSeries::from(y.clone());
+        assert_eq!(series.name(), None);
+        assert_eq!(series.into_inner(), y);
+    }
+
+    #[test]
+    fn test_time_series_name_in_rows_and_errors() {
+        let series = TimeSeries::from(Y.to_vec()).with_name("gdp");
+
+        let test = Adf {
+            lag: 0,
+            regression: Regression::Constant,
+        };
+        let report = series.run(&test).unwrap();
+        let row = series.to_row(&report, 0, AlphaLevel::FivePercent).unwrap();
+        assert_eq!(row.series_id, "gdp");
+        assert_eq!(row.statistic, report.test_statistic);
+
+        // errors name the series
+        let test = Adf {
+            lag: 20,
+            regression: Regression::Constant,
+        };
+        let err = series.run(&test).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::Series { name, source } if name == "gdp"
+                && matches!(**source, Error::NotEnoughSamples)
+        ));
+        assert!(err.to_string().contains("'gdp'"));
+
+        // unnamed series keep the error of the test
+        let series = TimeSeries::from(Y.to_vec());
+        assert!(matches!(series.run(&test), Err(Error::NotEnoughSamples)));
+    }
+}
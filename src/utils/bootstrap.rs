@@ -0,0 +1,132 @@
+// Copyright (c) 2022. Sebastien Soudan
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http:www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bootstrap p-values
+use nalgebra::{DMatrix, DVector, RealField, Scalar};
+use num_traits::Float;
+use rand::{Rng, RngExt, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::distrib::Regression;
+use crate::regression::{ols, residuals};
+use crate::tools::adf::adf_test;
+use crate::tools::diff;
+use crate::Error;
+
+/// Returns the bootstrap p-value of the Augmented Dickey-Fuller test of y.
+///
+/// The series is resampled under the unit-root null with a sieve bootstrap:
+/// 1. an AR(lag) model is fitted to Delta(y) by OLS and its centered residuals are kept
+/// 2. for each of the n_boot replications, Delta(y*) is rebuilt from the AR(lag) model
+///    with residuals drawn with replacement, y* is its cumulative sum from y[0], and the
+///    ADF statistic of y* is computed with the same lag and regression
+/// 3. the p-value is (1 + #{stat* <= stat}) / (1 + n_boot)
+///
+/// Returns `Error::InvalidParameter` if n_boot is 0.
+pub fn adf_bootstrap_p_value<R: Rng + ?Sized, F: RealField + Scalar + Float>(
+    rng: &mut R,
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+    n_boot: usize,
+) -> Result<F, Error> {
+    if n_boot == 0 {
+        return Err(Error::InvalidParameter(
+            "the number of bootstrap replications must be positive".into(),
+        ));
+    }
+
+    let test_statistic = adf_test(y, lag, regression)?.test_statistic;
+
+    let delta_y = diff(y)?;
+    let m = delta_y.len();
+
+    // AR(lag) fit of Delta(y) under the null
+    let (phi, errors) = if lag == 0 {
+        (DVector::zeros(0), delta_y.clone())
+    } else {
+        let target = delta_y.rows(lag, m - lag).into_owned();
+        let x = DMatrix::from_fn(m - lag, lag, |r, c| delta_y[r + lag - c - 1]);
+        let (phi, _t_stats) = ols(&target, &x)?;
+        let errors = residuals(&target, &x, &phi);
+        (phi, errors)
+    };
+    let errors = errors.add_scalar(-errors.mean());
+
+    let mut below = 0usize;
+    let mut delta_y_star = delta_y.clone();
+    let mut y_star = y.clone();
+    for _ in 0..n_boot {
+        for t in lag..m {
+            let ar = (0..lag).fold(F::zero(), |acc, i| acc + phi[i] * delta_y_star[t - i - 1]);
+            delta_y_star[t] = ar + errors[rng.random_range(0..errors.len())];
+        }
+        for t in 1..y_star.len() {
+            y_star[t] = y_star[t - 1] + delta_y_star[t - 1];
+        }
+
+        if adf_test(&y_star, lag, regression)?.test_statistic <= test_statistic {
+            below += 1;
+        }
+    }
+
+    F::from((1 + below) as f64 / (1 + n_boot) as f64).ok_or(Error::ConversionFailed)
+}
+
+/// Same as [`adf_bootstrap_p_value`] with a `ChaCha8Rng` seeded from seed, for
+/// reproducible results without managing an RNG.
+pub fn adf_bootstrap_p_value_seeded<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lag: usize,
+    regression: Regression,
+    n_boot: usize,
+    seed: u64,
+) -> Result<F, Error> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    adf_bootstrap_p_value(&mut rng, y, lag, regression, n_boot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::gen_ar_1;
+
+    #[test]
+    fn test_adf_bootstrap_p_value() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let stationary: DVector<f64> = gen_ar_1(&mut rng, 200, 0.0, 0.5, 1.0);
+        let p_value =
+            adf_bootstrap_p_value(&mut rng, &stationary, 1, Regression::Constant, 199).unwrap();
+        assert!(p_value < 0.05);
+
+        let walk: DVector<f64> = gen_ar_1(&mut rng, 200, 0.0, 1.0, 1.0);
+        let p_value = adf_bootstrap_p_value(&mut rng, &walk, 1, Regression::Constant, 199).unwrap();
+        assert!(p_value > 0.05);
+    }
+
+    #[test]
+    fn test_adf_bootstrap_p_value_seeded() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y: DVector<f64> = gen_ar_1(&mut rng, 100, 0.0, 0.9, 1.0);
+
+        let p_value = adf_bootstrap_p_value_seeded(&y, 2, Regression::Constant, 99, 7).unwrap();
+        let again = adf_bootstrap_p_value_seeded(&y, 2, Regression::Constant, 99, 7).unwrap();
+        assert_eq!(p_value, again);
+        assert!(p_value > 0.0 && p_value <= 1.0);
+
+        let res = adf_bootstrap_p_value_seeded(&y, 2, Regression::Constant, 0, 7);
+        assert!(matches!(res, Err(Error::InvalidParameter(_))));
+    }
+}
@@ -19,75 +19,766 @@ use rand::prelude::Distribution;
 use rand::Rng;
 use rand_distr::StandardNormal;
 
+use crate::distrib::Regression;
+use crate::regression::ols;
+use crate::tools::{add_deterministic_terms, lagged_diff, sorted, sorted_quantile};
+use crate::Error;
+
+mod bootstrap;
+
+pub use bootstrap::{adf_bootstrap_p_value, adf_bootstrap_p_value_seeded};
+
+/// Initial condition of the generated AR(1) process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArInit {
+    /// Start from Y_{-1} = 0, i.e. Y_0 = mu + sigma * e_0
+    Zero,
+    /// Draw Y_0 from the stationary distribution N(mu / (1 - delta), sigma^2 / (1 -
+    /// delta^2)). Falls back to `Zero` when |delta| >= 1 as there is no stationary
+    /// distribution.
+    Stationary,
+    /// Start from zero and discard the given number of initial observations
+    BurnIn(usize),
+}
+
 /// Generates AR(1) data:
 /// Y_t = mu + delta * Y_{t-1} + sigma * e_t
 /// where e_t is a standard normal random variable
+///
+/// The process starts from zero - see [`gen_ar_1_with_init`] for other initial
+/// conditions.
 pub fn gen_ar_1<R: Rng + ?Sized, F: RealField + Scalar + Float>(
+    rng: &mut R,
+    size: usize,
+    mu: F,
+    delta: F,
+    sigma: F,
+) -> DVector<F>
+where
+    StandardNormal: Distribution<F>,
+{
+    gen_ar_1_with_init(rng, size, mu, delta, sigma, ArInit::Zero)
+}
+
+/// Generates AR(1) data:
+/// Y_t = mu + delta * Y_{t-1} + sigma * e_t
+/// where e_t is a standard normal random variable
+/// and Y_0 is initialized according to `init`.
+pub fn gen_ar_1_with_init<R: Rng + ?Sized, F: RealField + Scalar + Float>(
     mut rng: &mut R,
     size: usize,
     mu: F,
     delta: F,
     sigma: F,
+    init: ArInit,
 ) -> DVector<F>
 where
     StandardNormal: Distribution<F>,
 {
-    let mut y = DVector::zeros(size);
+    let burn_in = match init {
+        ArInit::BurnIn(n) => n,
+        _ => 0,
+    };
+    let total = size + burn_in;
+
+    let mut y = DVector::zeros(total);
+    if total == 0 {
+        return y;
+    }
 
     let epsilon: F = StandardNormal.sample(&mut rng);
-    y[0] = mu + delta * F::from(0.0).unwrap() + sigma * epsilon;
+    let stationary = init == ArInit::Stationary && Float::abs(delta) < F::one();
+    y[0] = if stationary {
+        let mean = mu / (F::one() - delta);
+        let sd = sigma / Float::sqrt(F::one() - delta * delta);
+        mean + sd * epsilon
+    } else {
+        mu + delta * F::zero() + sigma * epsilon
+    };
 
-    for i in 1..size {
+    for i in 1..total {
         let epsilon: F = StandardNormal.sample(&mut rng);
         y[i] = mu + delta * y[i - 1] + sigma * epsilon;
     }
 
+    y.remove_rows(0, burn_in)
+}
+
+/// Generates AR(1) data:
+/// Y_t = mu + delta * Y_{t-1} + e_t
+/// where e_t is drawn from `dist`, e.g. a Student's t for fat-tailed innovations.
+///
+/// The process starts from zero.
+pub fn gen_ar_1_with_dist<R, F, D>(
+    mut rng: &mut R,
+    size: usize,
+    mu: F,
+    delta: F,
+    dist: D,
+) -> DVector<F>
+where
+    R: Rng + ?Sized,
+    F: RealField + Scalar + Float,
+    D: Distribution<F>,
+{
+    let mut y = DVector::zeros(size);
+    if size == 0 {
+        return y;
+    }
+
+    y[0] = mu + dist.sample(&mut rng);
+
+    for i in 1..size {
+        y[i] = mu + delta * y[i - 1] + dist.sample(&mut rng);
+    }
+
     y
 }
 
-fn gen_x<F: RealField + Float>(sz: usize) -> DMatrix<F> {
-    DMatrix::from_row_slice(
-        sz,
-        1,
-        Vec::from_iter(0..sz)
-            .into_iter()
-            .map(|x| F::from(x).unwrap())
-            .collect::<Vec<F>>()
-            .as_slice(),
-    )
+/// Generates a cointegrated pair:
+/// - x is a random walk X_t = X_{t-1} + e_t
+/// - y = beta * x + sigma * u_t
+///
+/// where e_t and u_t are independent standard normal random variables, so that
+/// y - beta * x is stationary. Returns (x, y), e.g. as the ground truth of
+/// `tools::cointegration::spread_adf(y, x, lag)`.
+pub fn gen_cointegrated_pair<R: Rng + ?Sized, F: RealField + Scalar + Float>(
+    mut rng: &mut R,
+    size: usize,
+    beta: F,
+    sigma: F,
+) -> (DVector<F>, DVector<F>)
+where
+    StandardNormal: Distribution<F>,
+{
+    let x = gen_ar_1(rng, size, F::zero(), F::one(), F::one());
+
+    let noise = DVector::<F>::from_iterator(size, StandardNormal.sample_iter(&mut rng).take(size));
+    let y = &x * beta + noise * sigma;
+
+    (x, y)
+}
+
+/// Generates a pair of independent random walks X_t = X_{t-1} + sigma * e_t: the
+/// non-cointegrated counterpart of [`gen_cointegrated_pair`].
+/// Returns (x, y).
+pub fn gen_independent_walks<R: Rng + ?Sized, F: RealField + Scalar + Float>(
+    rng: &mut R,
+    size: usize,
+    sigma: F,
+) -> (DVector<F>, DVector<F>)
+where
+    StandardNormal: Distribution<F>,
+{
+    let x = gen_ar_1(rng, size, F::zero(), F::one(), sigma);
+    let y = gen_ar_1(rng, size, F::zero(), F::one(), sigma);
+
+    (x, y)
+}
+
+/// Returns the column 0, 1, ..., sz - 1: a monotonically increasing index.
+/// Returns an error if sz is 0.
+fn gen_x<F: RealField + Float>(sz: usize) -> Result<DMatrix<F>, Error> {
+    if sz == 0 {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let x = (0..sz)
+        .map(|x| F::from(x).ok_or(Error::ConversionFailed))
+        .collect::<Result<Vec<F>, Error>>()?;
+
+    Ok(DMatrix::from_column_slice(sz, 1, &x))
 }
 
 /// Generate data as y = beta * x + mu
 /// where noise is drawn from a standard normal distribution
-/// Returns (x, y).
+/// Returns (x, y) or an error if sz is 0.
 pub fn gen_affine_data<F: RealField + Scalar + Float>(
     sz: usize,
     mu: F,
     beta: F,
-) -> (DMatrix<F>, DVector<F>) {
-    let x = gen_x(sz);
+) -> Result<(DMatrix<F>, DVector<F>), Error> {
+    let x = gen_x(sz)?;
     let y = (&x * beta).add_scalar(mu);
 
     let y = DVector::from_row_slice(y.as_slice());
-    (x, y)
+    Ok((x, y))
 }
 
 /// Generate data as y = beta * x + mu + noise
 /// where noise is drawn from a standard normal distribution
-/// Returns (x, y).
+/// Returns (x, y) or an error if sz is 0.
 pub fn gen_affine_data_with_whitenoise<R: Rng + ?Sized, F: RealField + Scalar + Float>(
-    mut rng: &mut R,
+    rng: &mut R,
     sz: usize,
     mu: F,
     beta: F,
-) -> (DMatrix<F>, DVector<F>)
+) -> Result<(DMatrix<F>, DVector<F>), Error>
 where
     StandardNormal: Distribution<F>,
 {
-    let x = gen_x(sz);
+    gen_affine_data_with_dist(rng, sz, mu, beta, StandardNormal)
+}
+
+/// Generate data as y = beta * x + mu + noise
+/// where noise is drawn from `dist`
+/// Returns (x, y) or an error if sz is 0.
+pub fn gen_affine_data_with_dist<R, F, D>(
+    mut rng: &mut R,
+    sz: usize,
+    mu: F,
+    beta: F,
+    dist: D,
+) -> Result<(DMatrix<F>, DVector<F>), Error>
+where
+    R: Rng + ?Sized,
+    F: RealField + Scalar + Float,
+    D: Distribution<F>,
+{
+    let x = gen_x(sz)?;
     let y = x.clone() * beta;
 
-    let noise = DVector::from_iterator(sz, StandardNormal.sample_iter(&mut rng).take(sz));
+    let noise = DVector::from_iterator(sz, dist.sample_iter(&mut rng).take(sz));
     let y = (y + noise).add_scalar(mu);
-    (x, y)
+    Ok((x, y))
+}
+
+/// Generate data as y = beta * x + mu + noise
+/// where noise is provided by the caller.
+/// Returns (x, y) or an error if sz is 0 or if `noise` does not have `sz` elements.
+pub fn gen_affine_data_with_noise<F: RealField + Scalar + Float>(
+    sz: usize,
+    mu: F,
+    beta: F,
+    noise: &DVector<F>,
+) -> Result<(DMatrix<F>, DVector<F>), Error> {
+    if noise.len() != sz {
+        return Err(Error::InvalidParameter(format!(
+            "noise has length {} but sz is {}",
+            noise.len(),
+            sz
+        )));
+    }
+
+    let x = gen_x(sz)?;
+    let y = x.clone() * beta;
+
+    let y = (y + noise).add_scalar(mu);
+    Ok((x, y))
+}
+
+/// Returns the residuals of the OLS regression of y on the deterministic terms
+/// implied by the regression:
+/// - `Regression::Constant`: y minus its mean
+/// - `Regression::ConstantAndTrend`: y minus its linear trend
+/// - `Regression::NoConstantNoTrend`: y unchanged
+pub fn detrend<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    regression: Regression,
+) -> Result<DVector<F>, Error> {
+    let mut x = DMatrix::zeros(y.len(), 0);
+    add_deterministic_terms(&mut x, regression)?;
+
+    if x.ncols() == 0 {
+        return Ok(y.clone());
+    }
+    if y.len() <= x.ncols() {
+        return Err(Error::NotEnoughSamples);
+    }
+
+    let (betas, _t_stats) = ols(y, &x)?;
+
+    Ok(y - x * betas)
+}
+
+/// Returns the Theil-Sen estimate of the slope of y against the time index: the median
+/// of the slopes (y[j] - y[i]) / (j - i) of all the pairs i < j.
+///
+/// Unlike the OLS trend of [`detrend`], it is robust to outliers - up to about 29% of
+/// them - e.g. to check whether a deterministic trend is plausible before choosing the
+/// regression. Returns NaN if y has fewer than 2 elements.
+///
+/// The n (n - 1) / 2 slopes are all stored: the cost is O(n^2) in time and memory.
+pub fn theil_sen_slope<F: RealField + Scalar + Float>(y: &DVector<F>) -> F {
+    let n = y.len();
+
+    let mut slopes = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+    for i in 0..n {
+        for j in i + 1..n {
+            slopes.push((y[j] - y[i]) / F::from(j - i).unwrap_or_else(F::nan));
+        }
+    }
+
+    slopes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted_quantile(&slopes, F::from(0.5).unwrap_or_else(F::nan))
+}
+
+/// Returns the q quantile of x, linearly interpolated between order statistics: the
+/// type 7 of Hyndman and Fan, as numpy's and R's default.
+///
+/// With x sorted, the quantile lies at the position q * (n - 1): q = 0 is the minimum,
+/// q = 1 the maximum, and a single element is every quantile. Returns NaN if x is
+/// empty. NaN values of x are sorted as equal to every value: the result is then
+/// meaningless.
+///
+/// Returns `Error::InvalidParameter` unless 0 <= q <= 1, e.g. for a NaN q.
+pub fn quantile<F: RealField + Scalar + Float>(x: &DVector<F>, q: F) -> Result<F, Error> {
+    if !(F::zero() <= q && q <= F::one()) {
+        return Err(Error::InvalidParameter(
+            "quantile must satisfy 0 <= q <= 1".into(),
+        ));
+    }
+
+    Ok(sorted_quantile(&sorted(x.as_slice()), q))
+}
+
+/// Returns y with the values below its lower_q quantile and above its upper_q quantile
+/// clipped to those quantiles, e.g. to limit the influence of data-entry spikes on the
+/// ADF regression. Interior values are untouched.
+///
+/// Quantiles are computed as [`quantile`].
+///
+/// Returns `Error::InvalidParameter` unless 0 <= lower_q <= upper_q <= 1, e.g. for a NaN
+/// quantile.
+pub fn winsorize<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    lower_q: F,
+    upper_q: F,
+) -> Result<DVector<F>, Error> {
+    if !(F::zero() <= lower_q && lower_q <= upper_q && upper_q <= F::one()) {
+        return Err(Error::InvalidParameter(
+            "quantiles must satisfy 0 <= lower_q <= upper_q <= 1".into(),
+        ));
+    }
+
+    if y.is_empty() {
+        return Ok(y.clone());
+    }
+
+    let sorted = sorted(y.as_slice());
+    let (lower, upper) = (
+        sorted_quantile(&sorted, lower_q),
+        sorted_quantile(&sorted, upper_q),
+    );
+
+    Ok(y.map(|v| Float::min(Float::max(v, lower), upper)))
+}
+
+/// Returns the cumulative sum y_0, y_0 + y_1, ..., y_0 + ... + y_{n-1}, e.g. to build
+/// an I(1) series from I(0) increments.
+///
+/// This is the inverse of the first difference up to the first element: the difference
+/// of the cumulative sum is y without its first element.
+pub fn cumsum<F: RealField + Scalar + Float>(y: &DVector<F>) -> DVector<F> {
+    let mut acc = F::zero();
+    y.map(|v| {
+        acc += v;
+        acc
+    })
+}
+
+/// Returns the seasonal difference y_t - y_{t-period}, e.g. with period 4 for quarterly
+/// or 12 for monthly data.
+///
+/// The first `period` observations have no seasonal lag and are dropped: the result has
+/// `y.len() - period` rows, or none if y is not longer than the period.
+///
+/// Returns `Error::InvalidParameter` if `period` is 0.
+pub fn seasonal_diff<F: RealField + Scalar + Float>(
+    y: &DVector<F>,
+    period: usize,
+) -> Result<DVector<F>, Error> {
+    if period == 0 {
+        return Err(Error::InvalidParameter("period must be positive".into()));
+    }
+
+    Ok(lagged_diff(y, period))
+}
+
+/// Checks that the time index increases by a constant positive step, e.g. a timestamp in
+/// seconds sampled every minute.
+///
+/// Returns `Error::IrregularSpacing` with the position of the first element whose
+/// distance to its predecessor differs from the first step, or is not positive.
+pub fn validate_evenly_spaced(index: &[i64]) -> Result<(), Error> {
+    if index.len() < 2 {
+        return Ok(());
+    }
+
+    let step = index[1] as i128 - index[0] as i128;
+    if step <= 0 {
+        return Err(Error::IrregularSpacing(1));
+    }
+
+    match (2..index.len()).find(|&i| index[i] as i128 - index[i - 1] as i128 != step) {
+        Some(i) => Err(Error::IrregularSpacing(i)),
+        None => Ok(()),
+    }
+}
+
+/// How [`resample_to_regular`] fills the points of the regular grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resampling {
+    /// The value of the last observation at or before the point
+    ForwardFill,
+    /// The linear interpolation between the observations around the point
+    Linear,
+}
+
+/// Resamples the values observed at a strictly increasing time index onto the regular
+/// grid index[0], index[0] + step, ..., up to the last index, e.g. to prepare irregular
+/// data for the tests which assume regular spacing.
+///
+/// - Returns `Error::IrregularSpacing` with the position of the first element that is not
+///   strictly greater than its predecessor, e.g. a duplicated timestamp.
+/// - Returns `Error::InvalidParameter` if index and values have different lengths or if
+///   step is not positive.
+pub fn resample_to_regular<F: RealField + Scalar + Float>(
+    index: &[i64],
+    values: &DVector<F>,
+    step: i64,
+    resampling: Resampling,
+) -> Result<DVector<F>, Error> {
+    if index.len() != values.len() {
+        return Err(Error::InvalidParameter(format!(
+            "index and values must have the same length: {} != {}",
+            index.len(),
+            values.len()
+        )));
+    }
+    if step <= 0 {
+        return Err(Error::InvalidParameter(format!(
+            "step must be positive: {}",
+            step
+        )));
+    }
+    if index.is_empty() {
+        return Err(Error::NotEnoughSamples);
+    }
+    if let Some(i) = (1..index.len()).find(|&i| index[i] <= index[i - 1]) {
+        return Err(Error::IrregularSpacing(i));
+    }
+
+    let (first, step) = (index[0] as i128, step as i128);
+    let size = (index[index.len() - 1] as i128 - first) / step + 1;
+    let size = usize::try_from(size).map_err(|_| Error::ConversionFailed)?;
+
+    let mut resampled = DVector::zeros(size);
+    // position of the last observation at or before the current point
+    let mut j = 0;
+    for (k, v) in resampled.iter_mut().enumerate() {
+        let t = first + k as i128 * step;
+        while j + 1 < index.len() && index[j + 1] as i128 <= t {
+            j += 1;
+        }
+
+        let at = index[j] as i128;
+        *v = match resampling {
+            Resampling::Linear if t > at => {
+                let next = index[j + 1] as i128;
+                let frac =
+                    F::from((t - at) as f64 / (next - at) as f64).ok_or(Error::ConversionFailed)?;
+                values[j] + frac * (values[j + 1] - values[j])
+            }
+            _ => values[j],
+        };
+    }
+
+    Ok(resampled)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    #[test]
+    fn test_winsorize_invalid_quantiles() {
+        let y = DVector::from_row_slice(&[1.0f64, 2.0, 3.0]);
+
+        for (lower_q, upper_q) in [(-0.1, 0.9), (0.1, 1.1), (0.9, 0.1), (f64::NAN, 0.9)] {
+            assert!(matches!(
+                winsorize(&y, lower_q, upper_q),
+                Err(Error::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_gen_affine_data_with_noise_length_mismatch() {
+        let noise = DVector::from_row_slice(&[0.1f64, -0.2, 0.3]);
+
+        let (x, y) = gen_affine_data_with_noise(3, 1.0, 2.0, &noise).unwrap();
+        assert_eq!(x, DMatrix::from_row_slice(3, 1, &[0.0, 1.0, 2.0]));
+        assert_eq!(y, DVector::from_row_slice(&[1.1, 2.8, 5.3]));
+
+        assert!(matches!(
+            gen_affine_data_with_noise(4, 1.0, 2.0, &noise),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            gen_affine_data_with_noise(2, 1.0, 2.0, &noise),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_detrend() {
+        let sz = 6;
+
+        let mu = 1.5;
+        let beta = 3.;
+
+        let noise = DVector::from_row_slice(&[0.1f64, -0.2, 0.05, 0.3, -0.1, -0.15]);
+        let (_, y) = gen_affine_data_with_noise(sz, mu, beta, &noise).unwrap();
+
+        let residuals = detrend(&y, Regression::ConstantAndTrend).unwrap();
+        assert_relative_eq!(residuals.sum(), 0.0, epsilon = 1e-9);
+        assert!(residuals.amax() < 0.5);
+
+        let residuals = detrend(&y, Regression::Constant).unwrap();
+        assert_relative_eq!(residuals, y.add_scalar(-y.mean()), epsilon = 1e-9);
+
+        let residuals = detrend(&y, Regression::NoConstantNoTrend).unwrap();
+        assert_eq!(residuals, y);
+
+        let y = DVector::from_row_slice(&[1.0f64, 2.0]);
+        assert!(detrend(&y, Regression::ConstantAndTrend).is_err());
+    }
+
+    #[test]
+    fn test_quantile() {
+        // numpy.quantile([3, 1, 4, 1, 5, 9, 2, 6], q)
+        let x = DVector::from_row_slice(&[3.0f64, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0]);
+        for (q, expected) in [
+            (0.0, 1.0),
+            (0.1, 1.0),
+            (0.25, 1.75),
+            (0.5, 3.5),
+            (0.9, 6.9),
+            (1.0, 9.0),
+        ] {
+            assert_relative_eq!(quantile(&x, q).unwrap(), expected, epsilon = 1e-12);
+        }
+
+        let single = DVector::from_element(1, 2.5f64);
+        assert_eq!(quantile(&single, 0.0).unwrap(), 2.5);
+        assert_eq!(quantile(&single, 0.7).unwrap(), 2.5);
+        assert_eq!(quantile(&single, 1.0).unwrap(), 2.5);
+
+        assert!(quantile(&DVector::<f64>::zeros(0), 0.5).unwrap().is_nan());
+
+        for q in [-0.1, 1.1, f64::NAN] {
+            assert!(matches!(quantile(&x, q), Err(Error::InvalidParameter(_))));
+        }
+    }
+
+    #[test]
+    fn test_theil_sen_slope() {
+        // y = 1 + 2t with a spike: most of the pairwise slopes are exactly 2
+        let mut y = DVector::from_fn(20, |i, _| 1.0f64 + 2.0 * i as f64);
+        y[7] += 100.0;
+        assert_eq!(theil_sen_slope(&y), 2.0);
+
+        // the OLS trend is pulled by the spike
+        let detrended = detrend(&y, Regression::ConstantAndTrend).unwrap();
+        let ols_slope = (y[19] - detrended[19] - (y[0] - detrended[0])) / 19.0;
+        assert!((ols_slope - 2.0).abs() > 0.1);
+
+        // slopes 1, 0.5 and 0: the median is 0.5
+        let y = DVector::from_row_slice(&[0.0f64, 1.0, 1.0]);
+        assert_eq!(theil_sen_slope(&y), 0.5);
+
+        assert!(theil_sen_slope(&DVector::from_element(1, 1.0f64)).is_nan());
+    }
+
+    #[test]
+    fn test_winsorize() {
+        let y = DVector::from_row_slice(&[
+            1.0f64, 2.0, 3.0, 4.0, 100.0, 5.0, 6.0, 7.0, 8.0, -50.0, 9.0,
+        ]);
+
+        // sorted: -50, 1, ..., 9, 100 - the 10% and 90% quantiles are 1 and 9
+        let clipped = winsorize(&y, 0.1, 0.9).unwrap();
+        assert_relative_eq!(clipped[4], 9.0, epsilon = 1e-12);
+        assert_relative_eq!(clipped[9], 1.0, epsilon = 1e-12);
+        for i in (0..y.len()).filter(|i| *i != 4 && *i != 9) {
+            assert_eq!(clipped[i], y[i]);
+        }
+
+        // interpolated quantiles: 0.05 * 10 = 0.5 between -50 and 1
+        let clipped = winsorize(&y, 0.05, 1.0).unwrap();
+        assert_relative_eq!(clipped[9], -24.5, epsilon = 1e-12);
+        assert_eq!(clipped[4], 100.0);
+
+        assert_eq!(winsorize(&y, 0.0, 1.0).unwrap(), y);
+    }
+
+    #[test]
+    fn test_cumsum() {
+        let y = DVector::from_row_slice(&[1.0f64, -2.0, 3.0, 0.5]);
+        let s = cumsum(&y);
+        assert_eq!(s, DVector::from_row_slice(&[1.0, -1.0, 2.0, 2.5]));
+        assert_eq!(crate::tools::diff(&s).unwrap(), y.rows(1, 3));
+
+        // the cumulative sum of white noise is a random walk: the unit root is not rejected
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let noise = gen_ar_1(&mut rng, 500, 0.0f64, 0.0, 1.0);
+        let critical_value = crate::distrib::dickeyfuller::get_critical_value(
+            Regression::Constant,
+            498,
+            crate::distrib::AlphaLevel::FivePercent,
+        )
+        .unwrap();
+        let report = crate::tools::adf::adf_test(&noise, 1, Regression::Constant).unwrap();
+        assert!(report.test_statistic < critical_value);
+        let report = crate::tools::adf::adf_test(&cumsum(&noise), 1, Regression::Constant).unwrap();
+        assert!(report.test_statistic > critical_value);
+    }
+
+    #[test]
+    fn test_seasonal_diff() {
+        // quarterly data: a fixed seasonal pattern on top of a +1 per year drift
+        let pattern = [10.0f64, 20.0, 5.0, 15.0];
+        let y = DVector::from_iterator(12, (0..12).map(|t| pattern[t % 4] + (t / 4) as f64));
+
+        let d = seasonal_diff(&y, 4).unwrap();
+        assert_eq!(d.len(), 8);
+        assert!(d.iter().all(|v| *v == 1.0));
+
+        let y = DVector::from_row_slice(&[1.0f64, 2.0, 4.0, 8.0, 16.0, 32.0]);
+        let d = seasonal_diff(&y, 4).unwrap();
+        assert_eq!(d, DVector::from_row_slice(&[15.0, 30.0]));
+
+        assert_eq!(
+            seasonal_diff(&y.rows(0, 4).into_owned(), 4).unwrap().len(),
+            0
+        );
+
+        assert!(matches!(
+            seasonal_diff(&y, 0),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_evenly_spaced() {
+        assert!(validate_evenly_spaced(&[]).is_ok());
+        assert!(validate_evenly_spaced(&[7]).is_ok());
+        assert!(validate_evenly_spaced(&[0, 60, 120, 180]).is_ok());
+
+        assert!(matches!(
+            validate_evenly_spaced(&[0, 60, 180, 240]),
+            Err(Error::IrregularSpacing(2))
+        ));
+        assert!(matches!(
+            validate_evenly_spaced(&[0, 60, 60, 120]),
+            Err(Error::IrregularSpacing(2))
+        ));
+        assert!(matches!(
+            validate_evenly_spaced(&[60, 0]),
+            Err(Error::IrregularSpacing(1))
+        ));
+    }
+
+    #[test]
+    fn test_resample_to_regular() {
+        let index = [0, 10, 30, 35, 60];
+        let values = DVector::from_row_slice(&[1.0f64, 2.0, 4.0, 10.0, 0.0]);
+
+        let filled = resample_to_regular(&index, &values, 10, Resampling::ForwardFill).unwrap();
+        assert_eq!(
+            filled,
+            DVector::from_row_slice(&[1.0, 2.0, 2.0, 4.0, 10.0, 10.0, 0.0])
+        );
+
+        let linear = resample_to_regular(&index, &values, 10, Resampling::Linear).unwrap();
+        let expected = [1.0, 2.0, 3.0, 4.0, 8.0, 4.0, 0.0];
+        assert_eq!(linear.len(), expected.len());
+        for (a, b) in linear.iter().zip(expected.iter()) {
+            assert_relative_eq!(*a, *b, epsilon = 1e-12);
+        }
+
+        // a regular index is left untouched
+        let index = [0, 10, 20];
+        let values = DVector::from_row_slice(&[1.0f64, 2.0, 3.0]);
+        assert_eq!(
+            resample_to_regular(&index, &values, 10, Resampling::Linear).unwrap(),
+            values
+        );
+
+        assert!(matches!(
+            resample_to_regular(&[0, 10, 10], &values, 10, Resampling::Linear),
+            Err(Error::IrregularSpacing(2))
+        ));
+        assert!(matches!(
+            resample_to_regular(&[0, 10], &values, 10, Resampling::Linear),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            resample_to_regular(&index, &values, 0, Resampling::Linear),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_gen_ar_1_init() {
+        let mu = 10.0f64;
+        let delta = 0.9;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1(&mut rng, 50, mu, delta, 1.0);
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y_zero = gen_ar_1_with_init(&mut rng, 50, mu, delta, 1.0, ArInit::Zero);
+        assert_eq!(y, y_zero);
+
+        // the stationary mean is mu / (1 - delta) = 100
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1_with_init(&mut rng, 50, mu, delta, 1.0, ArInit::Stationary);
+        assert_relative_eq!(y[0], 100.0, epsilon = 10.0);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1_with_init(&mut rng, 50, mu, delta, 1.0, ArInit::BurnIn(200));
+        assert_eq!(y.len(), 50);
+        assert_relative_eq!(y[0], 100.0, epsilon = 10.0);
+
+        // the first observation of the burnt-in series is the 201st of the full series
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y_full = gen_ar_1(&mut rng, 250, mu, delta, 1.0);
+        assert_eq!(y, y_full.rows(200, 50));
+    }
+
+    #[test]
+    fn test_gen_ar_1_with_dist() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y = gen_ar_1(&mut rng, 50, 1.0f64, 0.5, 1.0);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let y_dist = gen_ar_1_with_dist(&mut rng, 50, 1.0f64, 0.5, rand_distr::StandardNormal);
+        assert_eq!(y, y_dist);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let student_t = rand_distr::StudentT::new(3.0f64).unwrap();
+        let y = gen_ar_1_with_dist(&mut rng, 50, 1.0f64, 0.5, student_t);
+        assert_eq!(y.len(), 50);
+        assert!(y.iter().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn test_gen_affine_data_minimum_size() {
+        assert!(matches!(
+            gen_affine_data::<f64>(0, 1.0, 2.0),
+            Err(Error::NotEnoughSamples)
+        ));
+
+        let noise = DVector::from_row_slice(&[]);
+        assert!(gen_affine_data_with_noise::<f64>(0, 1.0, 2.0, &noise).is_err());
+
+        let (x, y) = gen_affine_data::<f64>(1, 1.0, 2.0).unwrap();
+        assert_eq!(x, DMatrix::from_row_slice(1, 1, &[0.0]));
+        assert_eq!(y, DVector::from_row_slice(&[1.0]));
+    }
 }